@@ -0,0 +1,506 @@
+use std::{
+    fs,
+    io::{self, Read, Write},
+    path::{Component, Path, PathBuf},
+    process::ExitCode,
+    sync::Arc,
+};
+
+use anyhow::{Context, Result};
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use ddcf::{
+    coverage,
+    encoding::{self, CodePage},
+    formatter::{self, Options, ParseError},
+    lint, project,
+    reformat::{self, FormatOptions},
+    render::{self, Format, GroupBy},
+    skeleton,
+};
+
+/// Daedalus docu comment formatter
+#[derive(Parser)]
+#[command(version, about)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Convert the doc comments of `.d` files to Markdown, HTML or JSON
+    Convert(ConvertArgs),
+    /// Convert every file listed in a Gothic `.src` file to its own page
+    Project(ProjectArgs),
+    /// Normalise the doc comments of `.d` files in place
+    Fmt(FmtArgs),
+    /// Add doc comment stubs to undocumented functions in place
+    Stub(StubArgs),
+    /// Check doc comments against the signatures they document
+    Lint(LintArgs),
+    /// Count the documented functions, constants and classes of `.d` files
+    Coverage(CoverageArgs),
+}
+
+#[derive(Args)]
+struct ConvertArgs {
+    /// Input files, reads from stdin when omitted or `-`
+    inputs: Vec<PathBuf>,
+    /// Output file, writes to stdout when omitted or `-`
+    #[arg(short, long)]
+    output: Option<PathBuf>,
+    /// Output format: mkdocs, commonmark, html or json
+    #[arg(short, long, default_value_t)]
+    format: Format,
+    /// Skip malformed doc blocks instead of stopping at the first one
+    #[arg(long)]
+    recover: bool,
+    /// Show the bodies of functions, prototypes and instances
+    #[arg(long)]
+    show_bodies: bool,
+    /// Do not link mentions of documented symbols
+    #[arg(long)]
+    no_links: bool,
+    /// Group the symbols by category, prefix or file, under a table of
+    /// contents
+    #[arg(short, long)]
+    group_by: Option<GroupBy>,
+    /// Sort the symbols of every group by name
+    #[arg(long, requires = "group_by")]
+    sort: bool,
+    /// Write an MkDocs `nav` entry for the groups of the output page to FILE
+    #[arg(long, value_name = "FILE", requires_all = ["group_by", "output"])]
+    nav: Option<PathBuf>,
+    /// MkDocs docs directory containing the output page, the `--nav` entry
+    /// links the page relative to it
+    #[arg(long, value_name = "DIR", default_value = "docs", requires = "nav")]
+    docs_dir: PathBuf,
+    /// Encoding of the input: utf-8, utf-16le, utf-16be, 1250, 1251 or 1252,
+    /// detected when omitted
+    #[arg(short, long)]
+    encoding: Option<CodePage>,
+}
+
+#[derive(Args)]
+struct ProjectArgs {
+    /// The `.src` file, e.g. `Content/Gothic.src`
+    src: PathBuf,
+    /// Output directory for the pages, `index.md` and `nav.yml`
+    #[arg(short, long, default_value = "docs")]
+    output: PathBuf,
+    /// Skip malformed doc blocks instead of stopping at the first one
+    #[arg(long)]
+    recover: bool,
+    /// Show the bodies of functions, prototypes and instances
+    #[arg(long)]
+    show_bodies: bool,
+    /// Do not link mentions of documented symbols
+    #[arg(long)]
+    no_links: bool,
+    /// Group the symbols of every page by category, prefix or file, under a
+    /// table of contents
+    #[arg(short, long)]
+    group_by: Option<GroupBy>,
+    /// Sort the symbols of every group by name
+    #[arg(long, requires = "group_by")]
+    sort: bool,
+    /// Encoding of the input: utf-8, utf-16le, utf-16be, 1250, 1251 or 1252,
+    /// detected when omitted
+    #[arg(short, long)]
+    encoding: Option<CodePage>,
+}
+
+#[derive(Args)]
+struct FmtArgs {
+    /// Files to format in place, formats stdin to stdout when omitted or `-`
+    inputs: Vec<PathBuf>,
+    /// Only list the files which are not formatted, do not change them
+    #[arg(long)]
+    check: bool,
+    /// Wrap doc lines longer than this, 0 to never wrap
+    #[arg(long, default_value_t = 100)]
+    width: usize,
+    /// Encoding of files without a byte order mark, detected when omitted.
+    /// Files are written back in their encoding
+    #[arg(short, long)]
+    encoding: Option<CodePage>,
+}
+
+#[derive(Args)]
+struct StubArgs {
+    /// Files to add stubs to, reads stdin and writes stdout when omitted or `-`
+    inputs: Vec<PathBuf>,
+    /// Print a unified diff instead of changing the files
+    #[arg(long)]
+    diff: bool,
+    /// Encoding of files without a byte order mark, detected when omitted.
+    /// Files are written back in their encoding
+    #[arg(short, long)]
+    encoding: Option<CodePage>,
+}
+
+#[derive(Args)]
+struct LintArgs {
+    /// Files to check, reads stdin when omitted or `-`
+    inputs: Vec<PathBuf>,
+    /// Fail on warnings too, not only on errors
+    #[arg(long)]
+    deny_warnings: bool,
+    /// Encoding of the input: utf-8, utf-16le, utf-16be, 1250, 1251 or 1252,
+    /// detected when omitted
+    #[arg(short, long)]
+    encoding: Option<CodePage>,
+}
+
+#[derive(Args)]
+struct CoverageArgs {
+    /// `.d` files, or `.src` files to count every file they list
+    #[arg(required = true)]
+    inputs: Vec<PathBuf>,
+    /// Output file, writes to stdout when omitted or `-`
+    #[arg(short, long)]
+    output: Option<PathBuf>,
+    /// Output format
+    #[arg(short, long, value_enum, default_value_t = ReportFormat::Text)]
+    format: ReportFormat,
+    /// Fail when less than this percentage is documented
+    #[arg(long, value_name = "PERCENT")]
+    threshold: Option<f64>,
+    /// Encoding of the input: utf-8, utf-16le, utf-16be, 1250, 1251 or 1252,
+    /// detected when omitted
+    #[arg(short, long)]
+    encoding: Option<CodePage>,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum ReportFormat {
+    /// Plain text tables
+    Text,
+    Json,
+    /// A badge and tables
+    Markdown,
+}
+
+fn is_stdio(path: &Path) -> bool {
+    path.as_os_str() == "-"
+}
+
+fn read_input(path: &Path, code_page: Option<CodePage>) -> Result<String> {
+    Ok(encoding::decode(&read_bytes(path)?, code_page))
+}
+
+fn read_bytes(path: &Path) -> Result<Vec<u8>> {
+    if is_stdio(path) {
+        let mut input = vec![];
+        io::stdin()
+            .read_to_end(&mut input)
+            .context("failed to read stdin")?;
+        Ok(input)
+    } else {
+        fs::read(path).with_context(|| format!("failed to read {}", path.display()))
+    }
+}
+
+fn write_output(path: Option<&Path>, output: &str) -> Result<()> {
+    match path {
+        Some(path) if !is_stdio(path) => {
+            fs::write(path, output).with_context(|| format!("failed to write {}", path.display()))
+        }
+        _ => io::stdout()
+            .write_all(output.as_bytes())
+            .context("failed to write stdout"),
+    }
+}
+
+/// Converts every input into one page, returns it and whether all of it
+/// parsed.
+fn convert(args: &ConvertArgs) -> Result<(String, bool)> {
+    let stdin = [PathBuf::from("-")];
+    let inputs = if args.inputs.is_empty() {
+        &stdin[..]
+    } else {
+        &args.inputs[..]
+    };
+
+    let mut files = vec![];
+    let mut ok = true;
+    for path in inputs {
+        let input = read_input(path, args.encoding)?;
+        let (parsed, errors) = formatter::parse_doc_comments(&input, args.recover);
+        for e in &errors {
+            eprintln!("{}: {}", path.display(), e);
+        }
+        ok &= !errors.iter().any(ParseError::is_error);
+        files.push((path.display().to_string(), parsed));
+    }
+
+    let options = Options {
+        show_bodies: args.show_bodies,
+        link_symbols: !args.no_links,
+        ..Options::default()
+    };
+    let Some(group_by) = args.group_by else {
+        let comments: Vec<_> = files.into_iter().flat_map(|(_, parsed)| parsed).collect();
+        return Ok((
+            render::render(args.format.renderer(), &comments, &options),
+            ok,
+        ));
+    };
+
+    let groups = render::group(
+        files
+            .iter()
+            .map(|(name, parsed)| (name.as_str(), parsed.as_slice())),
+        group_by,
+        args.sort,
+    );
+    if let (Some(nav), Some(output)) = (&args.nav, &args.output) {
+        // MkDocs nav paths are relative to the docs directory
+        let normal = |path: &Path| -> PathBuf {
+            path.components()
+                .filter(|c| *c != Component::CurDir)
+                .collect()
+        };
+        let page = normal(output);
+        let Ok(page) = page.strip_prefix(normal(&args.docs_dir)) else {
+            anyhow::bail!(
+                "{} is not in the docs directory {}, see --docs-dir",
+                output.display(),
+                args.docs_dir.display()
+            );
+        };
+        let title = output.file_stem().unwrap_or_default().to_string_lossy();
+        let page = page.to_string_lossy().replace('\\', "/");
+        write_output(Some(nav), &render::nav(&title, &page, &groups))?;
+    }
+    Ok((
+        render::render_grouped(args.format.renderer(), &groups, &options),
+        ok,
+    ))
+}
+
+/// Writes one page per documented file, an index and an MkDocs `nav`,
+/// returns whether all files parsed.
+fn convert_project(args: &ProjectArgs) -> Result<bool> {
+    let files = project::load_src(&args.src, args.recover, args.encoding)
+        .with_context(|| format!("failed to resolve {}", args.src.display()))?;
+    let options = Options {
+        show_bodies: args.show_bodies,
+        link_symbols: !args.no_links,
+        symbols: Some(Arc::new(project::symbol_index(&files))),
+        ..Options::default()
+    };
+
+    let mut ok = true;
+    for file in &files {
+        for e in &file.errors {
+            eprintln!("{}: {}", file.path.display(), e);
+        }
+        ok &= !file.errors.iter().any(ParseError::is_error);
+    }
+    if !ok && !args.recover {
+        return Ok(false);
+    }
+
+    for file in files.iter().filter(|f| !f.comments.is_empty()) {
+        let page = args.output.join(file.page_path());
+        if let Some(dir) = page.parent() {
+            fs::create_dir_all(dir)
+                .with_context(|| format!("failed to create {}", dir.display()))?;
+        }
+        let md = match args.group_by {
+            Some(group_by) => file.generate_grouped_md(&options, group_by, args.sort),
+            None => file.generate_md(&options),
+        };
+        write_output(Some(&page), &md)?;
+    }
+    fs::create_dir_all(&args.output)
+        .with_context(|| format!("failed to create {}", args.output.display()))?;
+    write_output(
+        Some(&args.output.join("index.md")),
+        &project::generate_index(&files),
+    )?;
+    write_output(
+        Some(&args.output.join("nav.yml")),
+        &project::generate_nav(&files),
+    )?;
+    Ok(ok)
+}
+
+/// A script read for rewriting, remembers its encoding to write it back.
+struct Script {
+    text: String,
+    code_page: CodePage,
+    bom: bool,
+}
+
+impl Script {
+    fn read(path: &Path, code_page: Option<CodePage>) -> Result<Script> {
+        let bytes = read_bytes(path)?;
+        let bom = encoding::detect_bom(&bytes);
+        let code_page = bom
+            .or(code_page)
+            .unwrap_or_else(|| encoding::detect(&bytes));
+        let script = Script {
+            text: encoding::decode(&bytes, Some(code_page)),
+            code_page,
+            bom: bom.is_some(),
+        };
+        if script.encode(&script.text) != bytes {
+            anyhow::bail!(
+                "{} can not be written back unchanged as {}, check --encoding",
+                path.display(),
+                code_page
+            );
+        }
+        Ok(script)
+    }
+
+    fn encode(&self, text: &str) -> Vec<u8> {
+        encoding::encode(text, self.code_page, self.bom)
+    }
+
+    /// Writes `text` to `path` in the encoding of the script, or to stdout.
+    fn write(&self, path: &Path, text: &str) -> Result<()> {
+        if is_stdio(path) {
+            io::stdout()
+                .write_all(&self.encode(text))
+                .context("failed to write stdout")
+        } else {
+            fs::write(path, self.encode(text))
+                .with_context(|| format!("failed to write {}", path.display()))
+        }
+    }
+}
+
+fn inputs_or_stdin(inputs: &[PathBuf]) -> Vec<PathBuf> {
+    if inputs.is_empty() {
+        vec![PathBuf::from("-")]
+    } else {
+        inputs.to_vec()
+    }
+}
+
+/// Formats every input in place, returns whether all of them parsed and, with
+/// `--check`, were formatted already.
+fn format_files(args: &FmtArgs) -> Result<bool> {
+    let options = FormatOptions {
+        width: Some(args.width).filter(|&width| width > 0),
+    };
+    let mut ok = true;
+    for path in inputs_or_stdin(&args.inputs) {
+        let script = Script::read(&path, args.encoding)?;
+        let (formatted, errors) = reformat::format_source(&script.text, &options);
+        for e in errors.iter().filter(|e| e.is_error()) {
+            eprintln!("{}: {}", path.display(), e);
+            ok = false;
+        }
+        let changed = formatted != script.text;
+        if args.check {
+            if changed {
+                println!("{}", path.display());
+                ok = false;
+            }
+        } else if changed || is_stdio(&path) {
+            script.write(&path, &formatted)?;
+        }
+    }
+    Ok(ok)
+}
+
+/// Adds stubs to every input in place, or prints them as a diff.
+fn add_stubs(args: &StubArgs) -> Result<()> {
+    for path in inputs_or_stdin(&args.inputs) {
+        let script = Script::read(&path, args.encoding)?;
+        if args.diff {
+            let name = path.to_string_lossy().replace('\\', "/");
+            write_output(None, &skeleton::stubs_diff(&script.text, &name))?;
+            continue;
+        }
+        let stubbed = skeleton::insert_stubs(&script.text);
+        if stubbed != script.text || is_stdio(&path) {
+            script.write(&path, &stubbed)?;
+        }
+    }
+    Ok(())
+}
+
+/// Lints every input, returns whether there were no errors (and, with
+/// `--deny-warnings`, no warnings).
+fn lint_files(args: &LintArgs) -> Result<bool> {
+    let mut ok = true;
+    for path in inputs_or_stdin(&args.inputs) {
+        let input = read_input(&path, args.encoding)?;
+        for e in lint::lint_source(&input) {
+            println!("{}: {}", path.display(), e);
+            ok &= !e.is_error() && !args.deny_warnings;
+        }
+    }
+    Ok(ok)
+}
+
+/// Writes the coverage report of every input, returns whether the coverage
+/// is at least `--threshold`.
+fn report_coverage(args: &CoverageArgs) -> Result<bool> {
+    let mut files = vec![];
+    for path in &args.inputs {
+        if project::is_src(path) {
+            let listed = project::resolve_src(path)
+                .with_context(|| format!("failed to resolve {}", path.display()))?;
+            for file in listed {
+                let input = read_input(&file, args.encoding)?;
+                files.push((file, input));
+            }
+        } else {
+            files.push((path.clone(), read_input(path, args.encoding)?));
+        }
+    }
+
+    let report = coverage::Report::new(files);
+    let output = match args.format {
+        ReportFormat::Text => report.to_text(),
+        ReportFormat::Json => report.to_json(),
+        ReportFormat::Markdown => report.to_markdown(),
+    };
+    write_output(args.output.as_deref(), &output)?;
+
+    let percent = report.total().percent();
+    match args.threshold {
+        Some(threshold) if percent < threshold => {
+            eprintln!(
+                "coverage {:.1}% is below the threshold of {}%",
+                percent, threshold
+            );
+            Ok(false)
+        }
+        _ => Ok(true),
+    }
+}
+
+fn run(cli: Cli) -> Result<bool> {
+    match cli.command {
+        Command::Convert(args) => {
+            let (md, ok) = convert(&args)?;
+            if ok || args.recover {
+                write_output(args.output.as_deref(), &md)?;
+            }
+            Ok(ok)
+        }
+        Command::Project(args) => convert_project(&args),
+        Command::Fmt(args) => format_files(&args),
+        Command::Stub(args) => add_stubs(&args).map(|_| true),
+        Command::Lint(args) => lint_files(&args),
+        Command::Coverage(args) => report_coverage(&args),
+    }
+}
+
+fn main() -> ExitCode {
+    match run(Cli::parse()) {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::FAILURE,
+        Err(e) => {
+            eprintln!("error: {:#}", e);
+            ExitCode::from(2)
+        }
+    }
+}
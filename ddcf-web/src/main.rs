@@ -0,0 +1,173 @@
+use std::time::Duration;
+
+use ddcf::{
+    encoding::{self, CodePage},
+    formatter::{self, Options, ParseError},
+    render::{self, preview, Format},
+};
+use leptos::*;
+use wasm_bindgen_futures::JsFuture;
+
+const EXAMPLE_INPUT: &str = r#"
+/// Sets up the visual of an NPC
+///
+/// @param npc NPC to be affected
+/// @param body_mesh mesh to be used as the body e.g. `HUM_BODY_NAKED0`
+/// @param body_tex body texture assigned to this body mesh
+/// @param skin body texture variant
+/// @param head_mesh head mesh
+/// @param head_tex head texture
+/// @param teeth_tex teeth texture
+/// @param armor_inst armor (C_ITEM instance) to be equipped or `-1` for no armor
+func void Mdl_SetVisualBody( var instance npc,
+                            var string body_mesh,
+                            var int body_tex,
+                            var int skin,
+                            var string head_mesh,
+                            var int head_tex,
+                            var int teeth_tex,
+                            var int armor_inst ) {};
+
+
+/// Display the document using the document manager ID
+///
+/// @param docID document manager ID
+func void Doc_Show(var int docID) {};
+
+
+/// Create a new instance of the document manager with the arrow showing players position on the map and returns its ID.
+///
+/// @return Returns the ID of the document manager instance.
+func int Doc_CreateMap() {};
+"#;
+
+/// Time without typing before the input is converted again.
+const DEBOUNCE: Duration = Duration::from_millis(300);
+
+fn main() {
+    mount_to_body(|| view! {
+        <h1>Daedalus docu-comment formatter (docu-comment to MD converter)</h1>
+        <p>Yes, it is not perfect, but it sort of works, ok?</p>
+        <App/>
+    })
+}
+
+#[component]
+fn App() -> impl IntoView {
+    let (input, _set_input) = create_signal(EXAMPLE_INPUT.to_string());
+    let (out, set_out) = create_signal("".to_string());
+    let (code_page, set_code_page) = create_signal(None::<CodePage>);
+    let (format, set_format) = create_signal(Format::default());
+    let (diagnostics, set_diagnostics) = create_signal(Vec::<ParseError>::new());
+    let backdrop = create_node_ref::<html::Pre>();
+
+    // converts once typing paused for `DEBOUNCE`, the last output stays when
+    // the input has errors
+    let pending = store_value(None);
+    create_effect(move |_| {
+        let (input, format) = (input(), format());
+        let convert = move || {
+            let (comments, errors) = formatter::parse_doc_comments(&input, true);
+            if !errors.iter().any(ParseError::is_error) {
+                set_out(render::render(format.renderer(), &comments, &Options::default()));
+            }
+            set_diagnostics(errors);
+        };
+        let previous = pending.get_value();
+        pending.set_value(set_timeout_with_handle(convert, DEBOUNCE).ok());
+        if let Some(previous) = previous {
+            previous.clear();
+        }
+    });
+    let preview = create_memo(move |_| match format() {
+        Format::Html => out(),
+        Format::Json => preview::page(&format!("```json\n{}\n```", out())),
+        Format::MkDocs | Format::CommonMark => preview::page(&out()),
+    });
+
+    // the input lines, marked where a diagnostic points
+    let highlighted = move || {
+        let diagnostics = diagnostics();
+        input()
+            .split('\n')
+            .enumerate()
+            .map(|(i, line)| {
+                let severity = diagnostics
+                    .iter()
+                    .filter(|d| d.line == i + 1)
+                    .map(|d| if d.is_error() { "error" } else { "warning" })
+                    .min(); // "error" before "warning"
+                view! { <span class=severity>{line.to_string()}"\n"</span> }
+            })
+            .collect_view()
+    };
+
+    view! {
+        <div>
+            <input type="file" accept=".d"
+                on:change=move |ev| {
+                    let files = event_target::<web_sys::HtmlInputElement>(&ev).files();
+                    if let Some(file) = files.and_then(|files| files.get(0)) {
+                        spawn_local(async move {
+                            if let Ok(buffer) = JsFuture::from(file.array_buffer()).await {
+                                let bytes = js_sys::Uint8Array::new(&buffer).to_vec();
+                                _set_input(encoding::decode(&bytes, code_page.get_untracked()));
+                            }
+                        });
+                    }
+                }
+            />
+            <select on:change=move |ev| set_code_page(event_target_value(&ev).parse().ok())>
+                <option value="auto">"Detect encoding"</option>
+                {CodePage::ALL
+                    .into_iter()
+                    .map(|cp| view! { <option value=cp.to_string()>{cp.to_string()}</option> })
+                    .collect_view()}
+            </select>
+            <select on:change=move |ev| set_format(event_target_value(&ev).parse().unwrap_or_default())>
+                {Format::ALL
+                    .into_iter()
+                    .map(|f| view! { <option value=f.to_string()>{f.to_string()}</option> })
+                    .collect_view()}
+            </select>
+        </div>
+
+        <div class="pane">
+            <div class="editor">
+                <pre class="backdrop" node_ref=backdrop aria-hidden="true">{highlighted}</pre>
+                <textarea
+                    prop:value=move || input()
+                    on:input = move |ev| {
+                        _set_input(event_target_value(&ev))
+                    }
+                    on:scroll = move |ev| {
+                        if let Some(backdrop) = backdrop.get() {
+                            let textarea = event_target::<web_sys::Element>(&ev);
+                            backdrop.set_scroll_top(textarea.scroll_top());
+                            backdrop.set_scroll_left(textarea.scroll_left());
+                        }
+                    }
+                    spellcheck="false"
+                >
+                    {move || input.get_untracked()}
+                </textarea>
+            </div>
+
+            <ul class="diagnostics">
+                {move || diagnostics()
+                    .into_iter()
+                    .map(|d| {
+                        let class = if d.is_error() { "error" } else { "warning" };
+                        view! { <li class=class>{d.to_string()}</li> }
+                    })
+                    .collect_view()}
+            </ul>
+        </div>
+
+        <textarea prop:value=out readonly rows="40" cols="50">
+            {move || out}
+        </textarea>
+
+        <iframe srcdoc=preview sandbox="" title="Preview" width="600" height="640"></iframe>
+    }
+}
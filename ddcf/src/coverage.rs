@@ -0,0 +1,401 @@
+//! How much of a script project is documented.
+//!
+//! ```
+//! use ddcf::coverage::Report;
+//!
+//! let source = "/// Shows it\nfunc void Doc_Show() {};\nfunc void Doc_Hide() {};\n";
+//! let report = Report::new(vec![("Doc.d".into(), source)]);
+//! assert_eq!(report.total().percent(), 50.0);
+//! ```
+
+use std::{collections::BTreeMap, path::PathBuf};
+
+use serde::Serialize;
+
+use crate::{model::SymbolKind, scan};
+
+/// A top level declaration and whether it has a `///` block.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct CoveredSymbol {
+    pub kind: SymbolKind,
+    pub name: String,
+    /// 1-based line of the declaration.
+    pub line: usize,
+    pub documented: bool,
+}
+
+/// Finds the top level functions, constants and classes of `input`,
+/// documented or not. Variables, prototypes and instances are not counted.
+pub fn scan_symbols(input: &str) -> Vec<CoveredSymbol> {
+    scan::statements(input)
+        .into_iter()
+        .filter_map(|statement| {
+            let start = statement.start;
+            let (keyword, name) = scan::declaration(&input[start..])?;
+            let kind = match keyword {
+                "func" => SymbolKind::Function,
+                "const" => SymbolKind::Const,
+                "class" => SymbolKind::Class,
+                _ => return None,
+            };
+            Some(CoveredSymbol {
+                kind,
+                name: name.to_string(),
+                line: input[..start].matches('\n').count() + 1,
+                documented: scan::is_documented(input, &statement),
+            })
+        })
+        .collect()
+}
+
+/// Documented out of all counted symbols.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct Coverage {
+    pub documented: usize,
+    pub total: usize,
+}
+
+impl Coverage {
+    fn add(&mut self, symbol: &CoveredSymbol) {
+        self.total += 1;
+        self.documented += usize::from(symbol.documented);
+    }
+
+    /// Percentage of documented symbols, 100 if there are none.
+    pub fn percent(&self) -> f64 {
+        if self.total == 0 {
+            100.0
+        } else {
+            self.documented as f64 * 100.0 / self.total as f64
+        }
+    }
+}
+
+fn coverage<'a>(symbols: impl IntoIterator<Item = &'a CoveredSymbol>) -> Coverage {
+    let mut coverage = Coverage::default();
+    for symbol in symbols {
+        coverage.add(symbol);
+    }
+    coverage
+}
+
+/// The prefix a symbol is grouped by: the name up to and including the first
+/// `_`, e.g. `Npc_` for `Npc_GetTalentSkill`.
+pub fn prefix(name: &str) -> &str {
+    match name.find('_') {
+        Some(i) if i > 0 && i + 1 < name.len() => &name[..=i],
+        _ => "",
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct FileReport {
+    pub path: PathBuf,
+    pub symbols: Vec<CoveredSymbol>,
+}
+
+/// Coverage of a set of files.
+#[derive(Debug, Clone, Serialize)]
+pub struct Report {
+    pub files: Vec<FileReport>,
+}
+
+impl Report {
+    /// Scans the sources of the given files.
+    pub fn new<S: AsRef<str>>(files: Vec<(PathBuf, S)>) -> Self {
+        Report {
+            files: files
+                .into_iter()
+                .map(|(path, source)| FileReport {
+                    path,
+                    symbols: scan_symbols(source.as_ref()),
+                })
+                .collect(),
+        }
+    }
+
+    fn symbols(&self) -> impl Iterator<Item = &CoveredSymbol> {
+        self.files.iter().flat_map(|file| &file.symbols)
+    }
+
+    pub fn total(&self) -> Coverage {
+        coverage(self.symbols())
+    }
+
+    pub fn by_file(&self) -> Vec<(String, Coverage)> {
+        self.files
+            .iter()
+            .map(|file| (file.path.display().to_string(), coverage(&file.symbols)))
+            .collect()
+    }
+
+    /// Coverage per [`prefix`], sorted, symbols without one are grouped under
+    /// `""`. Like names, prefixes are case-insensitive: `NPC_` is counted
+    /// under `Npc_` if that is seen first.
+    pub fn by_prefix(&self) -> Vec<(String, Coverage)> {
+        let mut prefixes: BTreeMap<String, (String, Coverage)> = BTreeMap::new();
+        for symbol in self.symbols() {
+            let prefix = prefix(&symbol.name);
+            prefixes
+                .entry(prefix.to_lowercase())
+                .or_insert_with(|| (prefix.to_string(), Coverage::default()))
+                .1
+                .add(symbol);
+        }
+        prefixes.into_values().collect()
+    }
+
+    pub fn by_kind(&self) -> BTreeMap<String, Coverage> {
+        let mut kinds: BTreeMap<String, Coverage> = BTreeMap::new();
+        for symbol in self.symbols() {
+            kinds
+                .entry(symbol.kind.name().to_string())
+                .or_default()
+                .add(symbol);
+        }
+        kinds
+    }
+
+    fn sections(&self) -> Vec<(&'static str, Vec<(String, Coverage)>)> {
+        let prefixes = self
+            .by_prefix()
+            .into_iter()
+            .map(|(prefix, coverage)| {
+                if prefix.is_empty() {
+                    ("(no prefix)".to_string(), coverage)
+                } else {
+                    (prefix, coverage)
+                }
+            })
+            .collect();
+        vec![
+            ("File", self.by_file()),
+            ("Kind", self.by_kind().into_iter().collect()),
+            ("Prefix", prefixes),
+        ]
+    }
+
+    /// Plain text tables per file, kind and prefix.
+    pub fn to_text(&self) -> String {
+        let rows: Vec<(String, Coverage)> = self
+            .sections()
+            .into_iter()
+            .flat_map(|(_, rows)| rows)
+            .collect();
+        let width = rows
+            .iter()
+            .map(|(name, _)| name.chars().count())
+            .max()
+            .unwrap_or(0)
+            .max(6);
+        let row = |name: &str, coverage: &Coverage| {
+            format!(
+                "{:<width$}  {:>10}  {:>6}  {:>7.1}%\n",
+                name,
+                coverage.documented,
+                coverage.total,
+                coverage.percent(),
+            )
+        };
+
+        let mut text = String::new();
+        for (title, rows) in self.sections() {
+            text.push_str(&format!(
+                "{:<width$}  {:>10}  {:>6}  {:>8}\n",
+                title, "Documented", "Total", "Coverage"
+            ));
+            for (name, coverage) in &rows {
+                text.push_str(&row(name, coverage));
+            }
+            text.push('\n');
+        }
+        text.push_str(&row("Total", &self.total()));
+        text
+    }
+
+    /// The coverage per file, kind and prefix as JSON.
+    pub fn to_json(&self) -> String {
+        #[derive(Serialize)]
+        struct Entry<'a> {
+            name: &'a str,
+            documented: usize,
+            total: usize,
+            percent: f64,
+        }
+        fn entries(rows: &[(String, Coverage)]) -> Vec<Entry<'_>> {
+            rows.iter()
+                .map(|(name, coverage)| Entry {
+                    name,
+                    documented: coverage.documented,
+                    total: coverage.total,
+                    percent: coverage.percent(),
+                })
+                .collect()
+        }
+        let total = self.total();
+        let files = self.by_file();
+        let kinds: Vec<_> = self.by_kind().into_iter().collect();
+        let prefixes = self.by_prefix();
+        let json = serde_json::json!({
+            "documented": total.documented,
+            "total": total.total,
+            "percent": total.percent(),
+            "files": entries(&files),
+            "kinds": entries(&kinds),
+            "prefixes": entries(&prefixes),
+        });
+        let mut json = serde_json::to_string_pretty(&json).expect("the report always serializes");
+        json.push('\n');
+        json
+    }
+
+    /// A shields.io badge with the total coverage followed by Markdown tables
+    /// per kind and prefix.
+    pub fn to_markdown(&self) -> String {
+        let total = self.total();
+        let percent = total.percent();
+        let color = if percent >= 80.0 {
+            "brightgreen"
+        } else if percent >= 50.0 {
+            "yellow"
+        } else {
+            "red"
+        };
+        let mut md = format!(
+            "![documentation coverage](https://img.shields.io/badge/docs-{:.0}%25-{})\n\n",
+            percent, color
+        );
+        md.push_str(&format!(
+            "**{:.1}%** documented ({} of {} symbols)\n",
+            percent, total.documented, total.total
+        ));
+        for (title, rows) in self.sections().into_iter().skip(1) {
+            md.push_str(&format!(
+                "\n| {} | Documented | Total | Coverage |\n| --- | ---: | ---: | ---: |\n",
+                title
+            ));
+            for (name, coverage) in rows {
+                md.push_str(&format!(
+                    "| {} | {} | {} | {:.1}% |\n",
+                    name.replace('_', "\\_"),
+                    coverage.documented,
+                    coverage.total,
+                    coverage.percent()
+                ));
+            }
+        }
+        md
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SOURCE: &str = "\
+/// Shows it
+func void Doc_Show() {};
+func void Doc_Hide() {};
+
+// not a doc block
+const int MAX = 2;
+
+/// The NPC
+class C_Npc { var int id; };
+var int counter;
+instance Hero(C_Npc) {};
+";
+
+    #[test]
+    fn symbols_and_documentation() {
+        let symbols = scan_symbols(SOURCE);
+        let found: Vec<_> = symbols
+            .iter()
+            .map(|symbol| {
+                (
+                    symbol.kind,
+                    symbol.name.as_str(),
+                    symbol.line,
+                    symbol.documented,
+                )
+            })
+            .collect();
+        assert_eq!(
+            found,
+            [
+                (SymbolKind::Function, "Doc_Show", 2, true),
+                (SymbolKind::Function, "Doc_Hide", 3, false),
+                (SymbolKind::Const, "MAX", 6, false),
+                (SymbolKind::Class, "C_Npc", 9, true),
+            ]
+        );
+    }
+
+    #[test]
+    fn totals() {
+        let report = Report::new(vec![("Doc.d".into(), SOURCE), ("Empty.d".into(), "")]);
+        let total = report.total();
+        assert_eq!((total.documented, total.total), (2, 4));
+        assert_eq!(total.percent(), 50.0);
+        assert_eq!(report.by_file()[1].1.percent(), 100.0);
+
+        let kinds = report.by_kind();
+        assert_eq!(
+            kinds["function"],
+            Coverage {
+                documented: 1,
+                total: 2
+            }
+        );
+        assert_eq!(
+            kinds["const"],
+            Coverage {
+                documented: 0,
+                total: 1
+            }
+        );
+    }
+
+    #[test]
+    fn prefixes_are_case_insensitive() {
+        let source = "\
+/// Attacks
+func void Npc_Attack() {};
+func void NPC_Flee() {};
+/// Rain
+func void Wld_SetRain() {};
+func void npc_Wait() {};
+func void Print() {};
+";
+        let report = Report::new(vec![("Npc.d".into(), source)]);
+        assert_eq!(
+            report.by_prefix(),
+            [
+                (
+                    "".to_string(),
+                    Coverage {
+                        documented: 0,
+                        total: 1
+                    }
+                ),
+                (
+                    "Npc_".to_string(),
+                    Coverage {
+                        documented: 1,
+                        total: 3
+                    }
+                ),
+                (
+                    "Wld_".to_string(),
+                    Coverage {
+                        documented: 1,
+                        total: 1
+                    }
+                ),
+            ]
+        );
+        assert_eq!(prefix("Npc_GetTalentSkill"), "Npc_");
+        assert_eq!(prefix("_Private"), "");
+        assert_eq!(prefix("Trailing_"), "");
+    }
+}
@@ -0,0 +1,261 @@
+//! Decoding and encoding of script files.
+//!
+//! Original Gothic scripts and most mods are not UTF-8 but use a legacy
+//! Windows code page: 1252 for German, English, French, Italian and Spanish,
+//! 1250 for Polish/Czech and 1251 for Russian versions.
+
+use std::{fmt, str::FromStr};
+
+use encoding_rs::{Encoding, UTF_16BE, UTF_16LE, UTF_8, WINDOWS_1250, WINDOWS_1251, WINDOWS_1252};
+
+/// Encoding of a script file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CodePage {
+    Utf8,
+    Utf16Le,
+    Utf16Be,
+    /// Central European (Polish, Czech)
+    Windows1250,
+    /// Cyrillic (Russian)
+    Windows1251,
+    /// Western European (German, English, French, Italian, Spanish)
+    Windows1252,
+}
+
+impl CodePage {
+    pub const ALL: [CodePage; 6] = [
+        CodePage::Utf8,
+        CodePage::Utf16Le,
+        CodePage::Utf16Be,
+        CodePage::Windows1250,
+        CodePage::Windows1251,
+        CodePage::Windows1252,
+    ];
+
+    fn encoding(self) -> &'static Encoding {
+        match self {
+            CodePage::Utf8 => UTF_8,
+            CodePage::Utf16Le => UTF_16LE,
+            CodePage::Utf16Be => UTF_16BE,
+            CodePage::Windows1250 => WINDOWS_1250,
+            CodePage::Windows1251 => WINDOWS_1251,
+            CodePage::Windows1252 => WINDOWS_1252,
+        }
+    }
+
+    fn from_encoding(encoding: &'static Encoding) -> Option<Self> {
+        CodePage::ALL
+            .into_iter()
+            .find(|code_page| code_page.encoding() == encoding)
+    }
+}
+
+impl fmt::Display for CodePage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.encoding().name())
+    }
+}
+
+impl FromStr for CodePage {
+    type Err = String;
+
+    /// Accepts the encoding names (`utf-8`, `windows-1250`, ...) as well as
+    /// the bare code page numbers (`1250`) and `cp1250`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim().to_ascii_lowercase();
+        let s = s.strip_prefix("cp").unwrap_or(&s);
+        match s {
+            "1250" => Ok(CodePage::Windows1250),
+            "1251" => Ok(CodePage::Windows1251),
+            "1252" => Ok(CodePage::Windows1252),
+            "utf8" => Ok(CodePage::Utf8),
+            _ => Encoding::for_label(s.as_bytes())
+                .and_then(CodePage::from_encoding)
+                .ok_or_else(|| format!("unsupported encoding `{}`", s)),
+        }
+    }
+}
+
+/// Bytes which are letters of Polish or Czech text in 1250 or of Western
+/// European text in 1252, with how common the letter is in either: e.g. `ł`
+/// vs. `³`, `ř` vs. `ø` or `č` vs. `è`. Bytes which are the same common
+/// letter in both, like `é`, tell nothing and are left out.
+const LETTERS: [(u8, u32, u32); 33] = [
+    (0x8A, 2, 0), // Š
+    (0x8C, 1, 1), // Ś, Œ
+    (0x8D, 1, 0), // Ť
+    (0x8E, 2, 0), // Ž
+    (0x8F, 1, 0), // Ź
+    (0x9A, 3, 0), // š
+    (0x9C, 2, 2), // ś, œ
+    (0x9D, 1, 0), // ť
+    (0x9E, 3, 0), // ž
+    (0x9F, 1, 0), // ź, Ÿ
+    (0xA3, 1, 1), // Ł, £
+    (0xA5, 1, 0), // Ą, ¥
+    (0xAF, 1, 0), // Ż, ¯
+    (0xB3, 3, 0), // ł, ³
+    (0xB9, 3, 0), // ą, ¹
+    (0xBF, 2, 2), // ż, ¿
+    (0xC0, 0, 1), // Ŕ, À
+    (0xC6, 1, 0), // Ć, Æ
+    (0xC8, 1, 1), // Č, È
+    (0xCA, 1, 0), // Ę, Ê
+    (0xCC, 1, 0), // Ě, Ì
+    (0xD1, 1, 1), // Ń, Ñ
+    (0xD8, 1, 0), // Ř, Ø
+    (0xE0, 0, 3), // ŕ, à
+    (0xE6, 1, 0), // ć, æ
+    (0xE8, 2, 3), // č, è
+    (0xEA, 3, 1), // ę, ê
+    (0xEC, 3, 1), // ě, ì
+    (0xEF, 1, 1), // ď, ï
+    (0xF1, 1, 2), // ń, ñ
+    (0xF2, 1, 1), // ň, ò
+    (0xF8, 3, 0), // ř, ø
+    (0xF9, 1, 1), // ů, ù
+];
+
+/// Guesses the code page of `bytes`. A byte order mark or valid UTF-8 wins,
+/// otherwise Cyrillic text is recognised by its runs of non-ASCII letters
+/// (German or Polish words only contain a few of them). Polish/Czech and
+/// Western European text are told apart by how often the letters specific to
+/// either code page occur, 1252 wins a tie.
+pub fn detect(bytes: &[u8]) -> CodePage {
+    if let Some((encoding, _)) = Encoding::for_bom(bytes) {
+        return CodePage::from_encoding(encoding).unwrap_or(CodePage::Utf8);
+    }
+    if std::str::from_utf8(bytes).is_ok() {
+        return CodePage::Utf8;
+    }
+
+    let letters = bytes.iter().filter(|&&b| b >= 0xC0).count();
+    let runs = bytes
+        .windows(2)
+        .filter(|pair| pair[0] >= 0xC0 && pair[1] >= 0xC0)
+        .count();
+    if runs * 2 > letters {
+        return CodePage::Windows1251;
+    }
+    let (mut central, mut western) = (0, 0);
+    for byte in bytes.iter().filter(|&&b| b >= 0x80) {
+        if let Some(&(_, in_1250, in_1252)) = LETTERS.iter().find(|(b, _, _)| b == byte) {
+            central += in_1250;
+            western += in_1252;
+        }
+    }
+    if central > western {
+        CodePage::Windows1250
+    } else {
+        CodePage::Windows1252
+    }
+}
+
+/// Decodes `bytes` to a string. A byte order mark always takes precedence,
+/// without one `code_page` is used or, if `None`, [`detect`]ed. Invalid
+/// sequences are replaced with `U+FFFD`.
+pub fn decode(bytes: &[u8], code_page: Option<CodePage>) -> String {
+    if let Some((encoding, bom_len)) = Encoding::for_bom(bytes) {
+        let (text, _) = encoding.decode_without_bom_handling(&bytes[bom_len..]);
+        return text.into_owned();
+    }
+    let code_page = code_page.unwrap_or_else(|| detect(bytes));
+    let (text, _) = code_page.encoding().decode_without_bom_handling(bytes);
+    text.into_owned()
+}
+
+/// Code page announced by a byte order mark at the start of `bytes`.
+pub fn detect_bom(bytes: &[u8]) -> Option<CodePage> {
+    Encoding::for_bom(bytes).and_then(|(encoding, _)| CodePage::from_encoding(encoding))
+}
+
+/// Encodes `text` to `code_page`, the inverse of [`decode`]. With `bom` a
+/// byte order mark is written first, for the Unicode encodings only.
+/// Characters missing from a Windows code page are written as HTML numeric
+/// character references (`&#1234;`).
+pub fn encode(text: &str, code_page: CodePage, bom: bool) -> Vec<u8> {
+    let mut bytes = vec![];
+    match code_page {
+        CodePage::Utf8 => {
+            if bom {
+                bytes.extend_from_slice(b"\xEF\xBB\xBF");
+            }
+            bytes.extend_from_slice(text.as_bytes());
+        }
+        CodePage::Utf16Le | CodePage::Utf16Be => {
+            let units = bom.then_some(0xFEFF).into_iter().chain(text.encode_utf16());
+            for unit in units {
+                if code_page == CodePage::Utf16Le {
+                    bytes.extend_from_slice(&unit.to_le_bytes());
+                } else {
+                    bytes.extend_from_slice(&unit.to_be_bytes());
+                }
+            }
+        }
+        _ => {
+            let (encoded, _, _) = code_page.encoding().encode(text);
+            bytes.extend_from_slice(&encoded);
+        }
+    }
+    bytes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn detect_text(text: &str, code_page: CodePage) -> CodePage {
+        detect(&encode(text, code_page, false))
+    }
+
+    #[test]
+    fn western_european_1252() {
+        for text in [
+            "Schließe die Tür und grüße den Wächter.",
+            "Questo è il mio perché.",
+            "Très chère sœur, voilà l'Œuvre complète.",
+            "¿Qué pasó, señor? ¡Mañana!",
+            "The price is £5.",
+        ] {
+            assert_eq!(
+                detect_text(text, CodePage::Windows1252),
+                CodePage::Windows1252,
+                "{}",
+                text
+            );
+        }
+    }
+
+    #[test]
+    fn central_european_1250() {
+        for text in [
+            "Zażółć gęślą jaźń.",
+            "Łuk jest zbyt słaby, weź miecz.",
+            "Přidej předmět.",
+            "Příliš žluťoučký kůň úpěl ďábelské ódy.",
+            "Začni hned, běž.",
+        ] {
+            assert_eq!(
+                detect_text(text, CodePage::Windows1250),
+                CodePage::Windows1250,
+                "{}",
+                text
+            );
+        }
+    }
+
+    #[test]
+    fn cyrillic_1251_and_unicode() {
+        let text = "Показывает документ, Привет!";
+        assert_eq!(
+            detect_text(text, CodePage::Windows1251),
+            CodePage::Windows1251
+        );
+        assert_eq!(detect(text.as_bytes()), CodePage::Utf8);
+        assert_eq!(detect_text(text, CodePage::Utf16Le), CodePage::Utf8);
+        assert_eq!(
+            detect(&encode(text, CodePage::Utf16Le, true)),
+            CodePage::Utf16Le
+        );
+    }
+}
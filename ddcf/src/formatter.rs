@@ -0,0 +1,1283 @@
+use std::{borrow::Cow, fmt, path::PathBuf, sync::Arc};
+
+use nom::{
+    branch::alt,
+    bytes::complete::{tag, tag_no_case},
+    character::complete::{
+        alpha1, alphanumeric1, char, digit1, line_ending, multispace0, multispace1,
+        not_line_ending, space0, space1,
+    },
+    combinator::{consumed, cut, map, opt, peek, recognize, verify},
+    error::{context, ContextError, ErrorKind, ParseError as _},
+    multi::{many0, many0_count, separated_list0},
+    sequence::{delimited, pair, preceded, tuple},
+    IResult, Offset,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    links::SymbolIndex,
+    render::{self, Renderer},
+    scan::{self, Token},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    /// The doc block could not be converted.
+    Error,
+    /// The doc block was converted, but part of it may not render as intended.
+    Warning,
+}
+
+/// Error produced when a doc block cannot be converted, or a warning about a
+/// block which was converted anyway.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ParseError {
+    /// 1-based line of the offending input.
+    pub line: usize,
+    /// 1-based column (in characters) of the offending input.
+    pub column: usize,
+    /// Name of the documented symbol, if it could be determined.
+    pub func_name: Option<String>,
+    pub message: String,
+    pub severity: Severity,
+}
+
+impl ParseError {
+    fn new(source: &str, at: &str, message: String) -> Self {
+        let (line, column) = position(source, source.offset(at));
+        ParseError {
+            line,
+            column,
+            func_name: None,
+            message,
+            severity: Severity::Error,
+        }
+    }
+
+    pub fn is_error(&self) -> bool {
+        self.severity == Severity::Error
+    }
+}
+
+/// 1-based line and column (in characters) of the byte `offset` in `source`.
+fn position(source: &str, offset: usize) -> (usize, usize) {
+    let before = &source[..offset];
+    let line = before.matches('\n').count() + 1;
+    let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+    (line, before[line_start..].chars().count() + 1)
+}
+
+/// Location of a doc block and its declaration in the parsed input.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Span {
+    /// Byte offset of the first `///` line, or of the `/**`.
+    pub start: usize,
+    /// Byte offset just after the declaration.
+    pub end: usize,
+    /// 1-based line of the first `///` line.
+    pub line: usize,
+    /// 1-based line the declaration ends on.
+    pub end_line: usize,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}, column {}", self.line, self.column)?;
+        if let Some(name) = &self.func_name {
+            write!(f, " (in `{}`)", name)?;
+        }
+        if self.severity == Severity::Warning {
+            write!(f, ": warning")?;
+        }
+        write!(f, ": {}", self.message)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// nom error carrying the position and a human readable message. The
+/// innermost `context` wins, as it is the most specific one.
+#[derive(Debug)]
+struct SyntaxError<'a> {
+    input: &'a str,
+    message: String,
+    has_context: bool,
+}
+
+impl<'a> SyntaxError<'a> {
+    fn failure(input: &'a str, message: String) -> nom::Err<Self> {
+        nom::Err::Failure(SyntaxError {
+            input,
+            message,
+            has_context: true,
+        })
+    }
+}
+
+impl<'a> nom::error::ParseError<&'a str> for SyntaxError<'a> {
+    fn from_error_kind(input: &'a str, kind: ErrorKind) -> Self {
+        SyntaxError {
+            input,
+            message: format!("unexpected input ({})", kind.description()),
+            has_context: false,
+        }
+    }
+
+    fn append(_: &'a str, _: ErrorKind, other: Self) -> Self {
+        other
+    }
+}
+
+impl<'a> ContextError<&'a str> for SyntaxError<'a> {
+    fn add_context(input: &'a str, ctx: &'static str, other: Self) -> Self {
+        if other.has_context {
+            other
+        } else {
+            SyntaxError {
+                input,
+                message: ctx.to_string(),
+                has_context: true,
+            }
+        }
+    }
+}
+
+type PResult<'a, O> = IResult<&'a str, O, SyntaxError<'a>>;
+
+/// The kinds of Daedalus types.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TypeKind {
+    Void,
+    Int,
+    Float,
+    String,
+    /// A function, e.g. a `var func` callback.
+    Func,
+    /// An instance of any class.
+    Instance,
+    /// An instance of a class named by the type, e.g. `C_NPC`.
+    Class,
+}
+
+impl TypeKind {
+    /// Kind of a type as written, types are case-insensitive.
+    pub fn of(ty: &str) -> TypeKind {
+        match ty.to_ascii_lowercase().as_str() {
+            "void" => TypeKind::Void,
+            "int" => TypeKind::Int,
+            "float" => TypeKind::Float,
+            "string" => TypeKind::String,
+            "func" => TypeKind::Func,
+            "instance" => TypeKind::Instance,
+            _ => TypeKind::Class,
+        }
+    }
+}
+
+/// A function parameter, e.g. `var int docID`, `var func callback`,
+/// `var C_NPC slf` or `var string names[MAX_NAMES]`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Parameter {
+    pub name: String,
+    /// Type as written, e.g. `int` or `C_NPC`.
+    #[serde(rename = "type")]
+    pub ty: String,
+    pub is_array: bool,
+    /// Size of an array parameter, if given as a number.
+    pub array_len: Option<usize>,
+    /// Size of an array parameter as written, a number or a constant name.
+    #[serde(default)]
+    pub array_size: Option<String>,
+}
+
+impl Parameter {
+    pub fn kind(&self) -> TypeKind {
+        TypeKind::of(&self.ty)
+    }
+
+    /// The type with the array size, e.g. `int` or `string[MAX_NAMES]`.
+    pub fn type_string(&self) -> String {
+        match (&self.array_size, self.is_array) {
+            (Some(size), _) => format!("{}[{}]", self.ty, size),
+            (None, true) => format!("{}[]", self.ty),
+            (None, false) => self.ty.clone(),
+        }
+    }
+}
+
+impl fmt::Display for Parameter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "var {} {}", self.ty, self.name)?;
+        if self.is_array {
+            write!(f, "[{}]", self.array_size.as_deref().unwrap_or_default())?;
+        }
+        Ok(())
+    }
+}
+
+/// Signature of a function, e.g. `func int Doc_Create()`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Signature {
+    pub return_type: String,
+    pub name: String,
+    pub params: Vec<Parameter>,
+}
+
+impl Signature {
+    /// Looks up a parameter by its name, ignoring case like Daedalus does.
+    pub fn param(&self, name: &str) -> Option<&Parameter> {
+        self.params
+            .iter()
+            .find(|p| p.name.eq_ignore_ascii_case(name))
+    }
+}
+
+/// The Daedalus symbol a doc block is attached to.
+#[derive(Debug)]
+pub enum Declaration {
+    Func(Signature),
+    /// `const <ty> <name>[<array_size>] = ...;`, the size only for arrays.
+    Const {
+        name: String,
+        ty: String,
+        array_size: Option<String>,
+    },
+    /// `var <ty> <name>[<array_size>];`, the size only for arrays.
+    Var {
+        name: String,
+        ty: String,
+        array_size: Option<String>,
+    },
+    Class {
+        name: String,
+    },
+    Prototype {
+        name: String,
+    },
+    Instance {
+        name: String,
+    },
+}
+
+impl Declaration {
+    pub fn name(&self) -> &str {
+        match self {
+            Declaration::Func(Signature { name, .. })
+            | Declaration::Const { name, .. }
+            | Declaration::Var { name, .. }
+            | Declaration::Class { name }
+            | Declaration::Prototype { name }
+            | Declaration::Instance { name } => name,
+        }
+    }
+
+    /// MkDocs admonition type used for this kind of declaration.
+    pub fn admonition(&self) -> &'static str {
+        match self {
+            Declaration::Func(_) => "function",
+            Declaration::Const { .. } => "const",
+            Declaration::Var { .. } => "var",
+            Declaration::Class { .. } => "class",
+            Declaration::Prototype { .. } => "prototype",
+            Declaration::Instance { .. } => "instance",
+        }
+    }
+
+    /// The signature, if this is a function.
+    pub fn signature(&self) -> Option<&Signature> {
+        match self {
+            Declaration::Func(signature) => Some(signature),
+            _ => None,
+        }
+    }
+
+    /// Type and array size of a constant or variable.
+    pub fn global_type(&self) -> Option<(&str, Option<&str>)> {
+        match self {
+            Declaration::Const { ty, array_size, .. } | Declaration::Var { ty, array_size, .. } => {
+                Some((ty, array_size.as_deref()))
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Settings for the conversion.
+#[derive(Debug, Clone)]
+pub struct Options {
+    /// Show the bodies of functions, prototypes and instances instead of
+    /// dropping them.
+    pub show_bodies: bool,
+    /// Link mentions of documented symbols to their heading.
+    pub link_symbols: bool,
+    /// Symbols to link to. When not set, [`generate_md`] links the symbols of
+    /// the rendered page.
+    pub symbols: Option<Arc<SymbolIndex>>,
+    /// Path of the rendered page relative to the output directory, links to
+    /// other pages are relative to it.
+    pub page: Option<PathBuf>,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options {
+            show_bodies: false,
+            link_symbols: true,
+            symbols: None,
+            page: None,
+        }
+    }
+}
+
+impl Options {
+    /// Links mentions of documented symbols in `text`, see
+    /// [`SymbolIndex::link_mentions`].
+    pub(crate) fn link_mentions(&self, text: &str, this: &str) -> String {
+        match &self.symbols {
+            Some(symbols) if self.link_symbols => {
+                symbols.link_mentions(text, self.page.as_deref(), this)
+            }
+            _ => text.to_string(),
+        }
+    }
+
+    /// URL of the docs of a class type, if it is documented.
+    pub(crate) fn type_url(&self, ty: &str) -> Option<String> {
+        match &self.symbols {
+            Some(symbols) if self.link_symbols && TypeKind::of(ty) == TypeKind::Class => {
+                symbols.link(ty, self.page.as_deref())
+            }
+            _ => None,
+        }
+    }
+
+    /// URL of a `@see` target, if it is a URL or a documented symbol.
+    pub(crate) fn see_url(&self, target: &str) -> Option<String> {
+        match &self.symbols {
+            _ if target.contains("://") => Some(target.to_string()),
+            Some(symbols) if self.link_symbols => symbols.link(target, self.page.as_deref()),
+            _ => None,
+        }
+    }
+
+    /// The options for rendering `comments` as one page, linking the symbols
+    /// of the page unless [`Options::symbols`] is already set.
+    pub(crate) fn for_page<'a>(
+        &self,
+        comments: impl IntoIterator<Item = &'a DocuComment>,
+    ) -> Options {
+        let mut options = self.clone();
+        if options.link_symbols && options.symbols.is_none() {
+            let mut symbols = SymbolIndex::new();
+            symbols.add(comments, None);
+            options.symbols = Some(Arc::new(symbols));
+        }
+        options
+    }
+}
+
+/// The tags understood in doc blocks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TagKind {
+    /// `@param <name> <description>`, see [`DocuComment::param_desc`]
+    Param,
+    /// `@return <description>`, see [`DocuComment::ret_stmt`]
+    Return,
+    /// `@see <symbol or URL>`, see [`DocuComment::see`]
+    See,
+    /// `@deprecated [reason]`, see [`DocuComment::deprecated`]
+    Deprecated,
+    /// `@since <version>`, see [`DocuComment::since`]
+    Since,
+    /// `@note <text>`, see [`DocuComment::notes`]
+    Note,
+    /// `@warning <text>`, see [`DocuComment::warnings`]
+    Warning,
+    /// `@example` followed by Daedalus code, see [`DocuComment::examples`]
+    Example,
+    /// `@category <name>` or `@group <name>`, see [`DocuComment::category`]
+    Category,
+}
+
+impl TagKind {
+    pub const ALL: [TagKind; 9] = [
+        TagKind::Param,
+        TagKind::Return,
+        TagKind::See,
+        TagKind::Deprecated,
+        TagKind::Since,
+        TagKind::Note,
+        TagKind::Warning,
+        TagKind::Example,
+        TagKind::Category,
+    ];
+
+    /// Name of the tag without the `@`.
+    pub fn name(self) -> &'static str {
+        match self {
+            TagKind::Param => "param",
+            TagKind::Return => "return",
+            TagKind::See => "see",
+            TagKind::Deprecated => "deprecated",
+            TagKind::Since => "since",
+            TagKind::Note => "note",
+            TagKind::Warning => "warning",
+            TagKind::Example => "example",
+            TagKind::Category => "category",
+        }
+    }
+
+    /// The tag named `name`, `group` is another name for `category`.
+    pub fn from_name(name: &str) -> Option<TagKind> {
+        if name == "group" {
+            return Some(TagKind::Category);
+        }
+        TagKind::ALL.into_iter().find(|kind| kind.name() == name)
+    }
+
+    /// Whether the tag may appear only once per block.
+    fn is_single(self) -> bool {
+        matches!(
+            self,
+            TagKind::Return | TagKind::Deprecated | TagKind::Since | TagKind::Category
+        )
+    }
+}
+
+/// A parsed doc block together with the declaration it documents.
+#[derive(Debug)]
+pub struct DocuComment {
+    /// Description paragraphs, separated by an empty line.
+    pub description: Option<String>,
+    /// `@param` names and descriptions in the order they were written.
+    pub param_desc: Option<Vec<(String, String)>>,
+    pub ret_stmt: Option<String>,
+    pub declaration: Declaration,
+    /// Source of the declaration shown in the code block.
+    pub decl_string: String,
+    /// `{ ... }` body of a function, prototype or instance.
+    pub body: Option<String>,
+    /// `@see` targets, symbol names or URLs.
+    pub see: Vec<String>,
+    /// `@deprecated` reason, empty if none was given.
+    pub deprecated: Option<String>,
+    /// `@since` version.
+    pub since: Option<String>,
+    pub notes: Vec<String>,
+    pub warnings: Vec<String>,
+    /// `@example` code with its common indentation removed.
+    pub examples: Vec<String>,
+    /// `@category` (or `@group`) the block is listed under when grouping.
+    pub category: Option<String>,
+    /// Tags which are not a [`TagKind`], as name and text.
+    pub unknown_tags: Vec<(String, String)>,
+    pub span: Span,
+    /// Every tag in the order it was written.
+    pub tags: Vec<TagPosition>,
+}
+
+/// Where a tag was written, see [`DocuComment::tags`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TagPosition {
+    /// Name of the tag without the `@`.
+    pub name: String,
+    /// Byte offset of the name in the parsed input.
+    pub offset: usize,
+    /// 1-based line and column of the name.
+    pub line: usize,
+    pub column: usize,
+}
+
+impl DocuComment {
+    /// Renders the block as a MkDocs Material admonition, see
+    /// [`render::MkDocs`].
+    pub fn generate_md(&self, options: &Options) -> String {
+        render::MkDocs.render_comment(self, options)
+    }
+
+    /// Source of the declaration as shown in the code block: with the body
+    /// if [`Options::show_bodies`] is set, else with an empty body for
+    /// functions and without one otherwise.
+    pub fn source(&self, options: &Options) -> String {
+        match (&self.body, &self.declaration) {
+            (Some(body), _) if options.show_bodies => format!("{} {};", self.decl_string, body),
+            (Some(_), Declaration::Func(_)) => format!("{} {{}};", self.decl_string),
+            _ => self.decl_string.clone(),
+        }
+    }
+}
+
+fn parse_doc_line(input: &str) -> PResult<'_, &str> {
+    let (input, _) = space0(input)?;
+    let (input, _) = tag("///")(input)?;
+    let (input, line) = not_line_ending(input)?;
+    let (input, _) = line_ending(input)?;
+    Ok((input, line.trim_end()))
+}
+
+/// Removes the indentation common to the non-empty `lines`, the space after
+/// `///` in most blocks, and trailing whitespace.
+fn dedent<'a>(lines: impl IntoIterator<Item = &'a str>) -> Vec<&'a str> {
+    let lines: Vec<&str> = lines.into_iter().map(str::trim_end).collect();
+    let indent = lines
+        .iter()
+        .filter(|line| !line.is_empty())
+        .map(|line| line.len() - line.trim_start().len())
+        .min()
+        .unwrap_or(0);
+    lines
+        .into_iter()
+        .map(|line| line.get(indent..).unwrap_or_default())
+        .collect()
+}
+
+/// Joins doc lines, keeping their indentation relative to each other, e.g.
+/// of nested lists or indented code, and the empty lines between
+/// paragraphs. Empty lines at the start and end are dropped.
+fn join_paragraphs<'a>(lines: impl IntoIterator<Item = &'a str>) -> Option<String> {
+    let lines = dedent(lines);
+    let first = lines.iter().position(|line| !line.is_empty())?;
+    let last = lines.iter().rposition(|line| !line.is_empty())?;
+    Some(lines[first..=last].join("\n"))
+}
+
+/// `///` lines which do not start a tag.
+fn parse_text_lines(input: &str) -> PResult<'_, Vec<&str>> {
+    many0(verify(parse_doc_line, |line: &str| {
+        !line.trim_start().starts_with('@')
+    }))(input)
+}
+
+/// Collects every `///` line up to the first tag line.
+fn parse_description(input: &str) -> PResult<'_, Option<String>> {
+    let (input, lines) = parse_text_lines(input)?;
+    Ok((input, join_paragraphs(lines)))
+}
+
+/// A `@tag` line and the `///` lines continuing it, up to the next tag.
+struct Tag<'a> {
+    name: &'a str,
+    /// Rest of the tag line, untrimmed.
+    first_line: &'a str,
+    more_lines: Vec<&'a str>,
+}
+
+impl Tag<'_> {
+    fn text(&self) -> Option<String> {
+        let more_lines = dedent(self.more_lines.iter().copied());
+        join_paragraphs(std::iter::once(self.first_line.trim_start()).chain(more_lines))
+    }
+
+    /// Text of the tag with line breaks and relative indentation kept.
+    fn code(&self) -> String {
+        let lines: Vec<&str> = std::iter::once(self.first_line)
+            .chain(self.more_lines.iter().copied())
+            .skip_while(|line| line.trim().is_empty())
+            .collect();
+        let indent = lines
+            .iter()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| line.len() - line.trim_start().len())
+            .min()
+            .unwrap_or(0);
+        let code: Vec<&str> = lines
+            .iter()
+            .map(|line| line.get(indent..).unwrap_or_default())
+            .collect();
+        code.join("\n").trim_end().to_string()
+    }
+}
+
+fn parse_tag(input: &str) -> PResult<'_, Tag<'_>> {
+    let (input, _) = space0(input)?;
+    let (input, _) = tag("///")(input)?;
+    let (input, _) = space0(input)?;
+    let (input, _) = char('@')(input)?;
+    let (input, name) = cut(context("expected a tag name after `@`", identifier))(input)?;
+    let (input, first_line) = not_line_ending(input)?;
+    let (input, _) = line_ending(input)?;
+    let (input, more_lines) = parse_text_lines(input)?;
+    Ok((
+        input,
+        Tag {
+            name,
+            first_line,
+            more_lines,
+        },
+    ))
+}
+
+fn identifier(input: &str) -> PResult<'_, &str> {
+    recognize(pair(
+        alt((alpha1, tag("_"))),
+        many0_count(alt((alphanumeric1, tag("_")))),
+    ))(input)
+}
+
+/// A parsed declaration, the source shown in the docs and its body, if any.
+type Decl<'a> = (Declaration, &'a str, Option<&'a str>);
+
+fn parse_func(input: &str) -> PResult<'_, Decl<'_>> {
+    let (input, (header, signature)) = consumed(function_signature)(input)?;
+    let (input, _) = skip_comments(input)?;
+    // after the signature only the body fits, keep its error
+    let (input, body) = cut(parse_body)(input)?;
+    Ok((input, (Declaration::Func(signature), header, Some(body))))
+}
+
+fn parameter(input: &str) -> PResult<'_, Parameter> {
+    let (input, _) = tag_no_case("var")(input)?;
+    cut(|input| {
+        let (input, ty) = context(
+            "expected a parameter type",
+            preceded(multispace1, identifier),
+        )(input)?;
+        let (input, name) = context(
+            "expected a parameter name",
+            preceded(multispace1, identifier),
+        )(input)?;
+        let (input, array_size) = opt(array_size)(input)?;
+        Ok((
+            input,
+            Parameter {
+                name: name.to_string(),
+                ty: ty.to_string(),
+                is_array: array_size.is_some(),
+                array_len: array_size.flatten().and_then(|size| size.parse().ok()),
+                array_size: array_size.flatten().map(str::to_string),
+            },
+        ))
+    })(input)
+}
+
+/// `[<size>]` of an array, the size is a number or a constant name.
+fn array_size(input: &str) -> PResult<'_, Option<&str>> {
+    delimited(
+        pair(multispace0, char('[')),
+        delimited(multispace0, opt(alt((digit1, identifier))), multispace0),
+        context("expected `]`", char(']')),
+    )(input)
+}
+
+fn function_signature(input: &str) -> PResult<'_, Signature> {
+    let (input, _) = tag_no_case("func")(input)?;
+    let (input, _) = multispace1(input)?;
+    cut(|input| {
+        let (input, return_type) = context("expected a return type", identifier)(input)?;
+        let (input, _) = multispace1(input)?;
+        let (input, name) = context("expected a function name", identifier)(input)?;
+        let (input, _) = multispace0(input)?;
+        let (input, _) = context("expected `(`", char('('))(input)?;
+        let (input, _) = multispace0(input)?;
+        let (input, params) =
+            separated_list0(delimited(multispace0, char(','), multispace0), parameter)(input)?;
+        let (input, _) = multispace0(input)?;
+        let (input, _) = context("expected `,` or `)`", char(')'))(input)?;
+
+        Ok((
+            input,
+            Signature {
+                return_type: return_type.to_string(),
+                name: name.to_string(),
+                params,
+            },
+        ))
+    })(input)
+}
+
+/// Parses a function signature such as `func void Doc_Show(var int docID)`,
+/// anything after the closing `)` is ignored.
+pub fn parse_function_signature(input: &str) -> Result<Signature, ParseError> {
+    let trimmed = input.trim_start();
+    match function_signature(trimmed) {
+        Ok((_, signature)) => Ok(signature),
+        Err(nom::Err::Error(e)) | Err(nom::Err::Failure(e)) => {
+            Err(ParseError::new(input, e.input, e.message))
+        }
+        Err(nom::Err::Incomplete(_)) => unreachable!("complete parsers only"),
+    }
+}
+
+/// Skips whitespace and comments.
+fn skip_comments(input: &str) -> PResult<'_, ()> {
+    let code = scan::tokens(input)
+        .find(|(_, token)| *token != Token::Comment)
+        .map_or(input.len(), |(range, _)| range.start);
+    Ok((&input[code..], ()))
+}
+
+/// Skips a `{ ... }` block and the `;` after it. Nested braces are balanced,
+/// braces inside strings and `//` or `/* */` comments are ignored.
+fn parse_body(input: &str) -> PResult<'_, &str> {
+    let (_, _) = context("expected `{`", char('{'))(input)?;
+    let mut depth = 0;
+    for (range, token) in scan::tokens(input) {
+        match token {
+            Token::Char('{') => depth += 1,
+            Token::Char('}') => {
+                depth -= 1;
+                if depth == 0 {
+                    let (body, rest) = input.split_at(range.end);
+                    let (rest, _) = multispace0(rest)?;
+                    let (rest, _) = context("expected `;` after `}`", cut(char(';')))(rest)?;
+                    return Ok((rest, body));
+                }
+            }
+            _ => {}
+        }
+    }
+    Err(SyntaxError::failure(
+        input,
+        "unterminated body, missing `}`".to_string(),
+    ))
+}
+
+/// Takes everything up to and including the `;` ending a statement, skipping
+/// over string literals.
+fn statement(input: &str) -> PResult<'_, &str> {
+    let mut in_string = false;
+    for (i, c) in input.char_indices() {
+        match c {
+            '"' => in_string = !in_string,
+            ';' if !in_string => return Ok((&input[i + 1..], &input[..i + 1])),
+            _ => {}
+        }
+    }
+    Err(nom::Err::Error(SyntaxError::from_error_kind(
+        input,
+        ErrorKind::TakeUntil,
+    )))
+}
+
+/// `const <type> <name> ...;` and `var <type> <name> ...;`
+fn parse_variable(input: &str) -> PResult<'_, Decl<'_>> {
+    let (_, (keyword, _, ty, _, name, array_size)) = tuple((
+        alt((tag_no_case("const"), tag_no_case("var"))),
+        multispace1,
+        cut(context("expected a type", identifier)),
+        multispace1,
+        cut(context("expected a name", identifier)),
+        opt(array_size),
+    ))(input)?;
+    let (rest, decl) = cut(context("expected `;`", statement))(input)?;
+    let (name, ty) = (name.to_string(), ty.to_string());
+    let array_size = array_size.flatten().map(str::to_string);
+    let declaration = if keyword.eq_ignore_ascii_case("const") {
+        Declaration::Const {
+            name,
+            ty,
+            array_size,
+        }
+    } else {
+        Declaration::Var {
+            name,
+            ty,
+            array_size,
+        }
+    };
+    Ok((rest, (declaration, decl, None)))
+}
+
+/// `class <name> { ... };`, the body with all the fields is shown.
+fn parse_class(input: &str) -> PResult<'_, Decl<'_>> {
+    let (rest, (class, (_, _, name, _, _))) = consumed(tuple((
+        tag_no_case("class"),
+        multispace1,
+        cut(context("expected a class name", identifier)),
+        multispace0,
+        parse_body,
+    )))(input)?;
+    let name = name.to_string();
+    Ok((rest, (Declaration::Class { name }, class, None)))
+}
+
+/// `prototype <name>(<class>) { ... };` and `instance <name>(<class>) { ... };`
+fn parse_instance(input: &str) -> PResult<'_, Decl<'_>> {
+    let (input, (header, (keyword, _, (name, _, _, _, _, _, _)))) = consumed(tuple((
+        alt((tag_no_case("prototype"), tag_no_case("instance"))),
+        multispace1,
+        cut(tuple((
+            context("expected a name", identifier),
+            multispace0,
+            context("expected `(`", char('(')),
+            multispace0,
+            context("expected a class name", identifier),
+            multispace0,
+            context("expected `)`", char(')')),
+        ))),
+    )))(input)?;
+    let (input, _) = multispace0(input)?;
+    let (input, body) = alt((map(char(';'), |_| None), map(parse_body, Some)))(input)?;
+    let name = name.to_string();
+    let declaration = if keyword.eq_ignore_ascii_case("prototype") {
+        Declaration::Prototype { name }
+    } else {
+        Declaration::Instance { name }
+    };
+    Ok((input, (declaration, header, body)))
+}
+
+fn parse_declaration(input: &str) -> PResult<'_, Decl<'_>> {
+    let (input, _) = multispace0(input)?;
+    context(
+        "expected a `func`, `const`, `var`, `class`, `prototype` or `instance` declaration",
+        alt((parse_func, parse_variable, parse_class, parse_instance)),
+    )(input)
+}
+
+/// Splits a `@param` tag into the parameter name and its description.
+fn parse_param<'a>(tag: &Tag<'a>) -> PResult<'a, (&'a str, String)> {
+    let (rest, name) = cut(context(
+        "expected `@param <name> <description>`",
+        preceded(space1, identifier),
+    ))(tag.first_line)?;
+    let more_lines = dedent(tag.more_lines.iter().copied());
+    let desc = join_paragraphs(std::iter::once(rest.trim()).chain(more_lines));
+    Ok((rest, (name, desc.unwrap_or_default())))
+}
+
+/// Parses a doc block and its declaration. Unknown tags are kept and returned
+/// as warnings together with their position.
+fn parse_doc_comment(input: &str) -> PResult<'_, (DocuComment, Vec<(&str, String)>)> {
+    let block = input;
+    let (input, _) = context("expected a `///` doc comment", peek(parse_doc_line))(input)?;
+    let (input, description) = parse_description(input)?;
+    let (input, tags) = many0(parse_tag)(input)?;
+
+    let mut params = vec![];
+    let mut ret_stmt = None;
+    let mut ret_at = None;
+    let mut see = vec![];
+    let mut deprecated = None;
+    let mut since = None;
+    let mut notes = vec![];
+    let mut warnings = vec![];
+    let mut examples = vec![];
+    let mut category = None;
+    let mut unknown_tags = vec![];
+    let mut diagnostics = vec![];
+    let mut seen = vec![];
+    for tag in &tags {
+        let Some(kind) = TagKind::from_name(tag.name) else {
+            diagnostics.push((tag.name, format!("unknown tag `@{}`", tag.name)));
+            unknown_tags.push((tag.name.to_string(), tag.text().unwrap_or_default()));
+            continue;
+        };
+        if kind.is_single() && seen.contains(&kind) {
+            return Err(SyntaxError::failure(
+                tag.name,
+                format!("duplicate @{}", tag.name),
+            ));
+        }
+        seen.push(kind);
+        match kind {
+            TagKind::Param => params.push(parse_param(tag)?.1),
+            TagKind::Return => {
+                ret_at = Some(tag.name);
+                ret_stmt = tag.text();
+            }
+            TagKind::Deprecated => deprecated = Some(tag.text().unwrap_or_default()),
+            TagKind::Since | TagKind::See | TagKind::Category => {
+                let Some(text) = tag.text() else {
+                    return Err(SyntaxError::failure(
+                        tag.name,
+                        format!("@{} needs a value", tag.name),
+                    ));
+                };
+                match kind {
+                    TagKind::Since => since = Some(text),
+                    TagKind::Category => category = Some(text),
+                    _ => see.push(text),
+                }
+            }
+            TagKind::Note => notes.extend(tag.text()),
+            TagKind::Warning => warnings.extend(tag.text()),
+            TagKind::Example => examples.push(tag.code()),
+        }
+    }
+
+    let (input, (declaration, decl_string, body)) = parse_declaration(input)?;
+
+    match ret_at {
+        Some(ret_at) if declaration.signature().is_none() => {
+            return Err(SyntaxError::failure(
+                ret_at,
+                format!(
+                    "@return is only valid on functions, not on `{}`",
+                    declaration.name()
+                ),
+            ))
+        }
+        _ => {}
+    }
+
+    for (param, _) in &params {
+        let signature = declaration.signature();
+        if signature.and_then(|s| s.param(param)).is_none() {
+            return Err(SyntaxError::failure(
+                param,
+                format!("@param `{}` has no matching parameter", param),
+            ));
+        }
+    }
+
+    let comment = DocuComment {
+        description,
+        param_desc: Some(
+            params
+                .into_iter()
+                .map(|(name, desc)| (name.to_string(), desc))
+                .collect(),
+        ),
+        ret_stmt,
+        declaration,
+        decl_string: decl_string.to_string(),
+        body: body.map(str::to_string),
+        see,
+        deprecated,
+        since,
+        notes,
+        warnings,
+        examples,
+        category,
+        unknown_tags,
+        span: Span::default(),
+        // offsets relative to the block, parse_doc_comments makes them absolute
+        tags: tags
+            .iter()
+            .map(|tag| TagPosition {
+                name: tag.name.to_string(),
+                offset: block.offset(tag.name),
+                line: 0,
+                column: 0,
+            })
+            .collect(),
+    };
+    Ok((input, (comment, diagnostics)))
+}
+
+/// A doc block as `///` lines for the parser. `/** */` blocks are rewritten
+/// into them, with offsets into the rewritten text mapped back to the input.
+struct BlockText<'a> {
+    text: Cow<'a, str>,
+    /// Offset of the block in the input.
+    start: usize,
+    /// Offset in `text`, offset in the input and length of the added `/// `
+    /// of every rewritten line and of the code after the comment.
+    lines: Vec<(usize, usize, usize)>,
+}
+
+impl<'a> BlockText<'a> {
+    /// The block at `start` and the code after it up to `until`. Leading `*`s
+    /// of `/** */` lines and the spaces before them are dropped, as are empty
+    /// lines next to `/**` and `*/`.
+    fn new(input: &'a str, start: usize, until: usize) -> Self {
+        let rest = &input[start..until];
+        let Some(comment) = rest.strip_prefix("/**") else {
+            return BlockText {
+                text: Cow::Borrowed(&input[start..]),
+                start,
+                lines: vec![],
+            };
+        };
+        let close = comment.find("*/").map_or(rest.len(), |i| 3 + i);
+        let after = (close + 2).min(rest.len());
+
+        let mut text = String::new();
+        let mut lines = vec![];
+        let body = &rest[3..close];
+        let count = body.split('\n').count();
+        for (i, line) in body.split('\n').enumerate() {
+            let mut content = line.trim_end();
+            if i > 0 {
+                content = content.trim_start();
+                if let Some(starred) = content.strip_prefix('*') {
+                    content = starred.strip_prefix(' ').unwrap_or(starred);
+                }
+            } else {
+                content = content.trim_start();
+            }
+            if (i == 0 || i + 1 == count) && content.is_empty() {
+                continue;
+            }
+            lines.push((text.len(), start + rest.offset(content), 4));
+            text.push_str("/// ");
+            text.push_str(content);
+            text.push('\n');
+        }
+        if lines.is_empty() {
+            lines.push((0, start + 3, 4));
+            text.push_str("///\n");
+        }
+        lines.push((text.len(), start + after, 0));
+        text.push_str(&rest[after..]);
+        BlockText {
+            text: Cow::Owned(text),
+            start,
+            lines,
+        }
+    }
+
+    fn input_offset(&self, offset: usize) -> usize {
+        match self.lines.iter().rev().find(|(at, _, _)| *at <= offset) {
+            Some(&(at, input, prefix)) => input + (offset - at).saturating_sub(prefix),
+            None => self.start + offset,
+        }
+    }
+
+    /// Offset in the input of `at`, a slice of the text.
+    fn input_offset_of(&self, at: &str) -> usize {
+        self.input_offset(self.text.as_ref().offset(at))
+    }
+}
+
+/// Block offsets in ascending order, each once.
+fn sorted(mut blocks: Vec<usize>) -> Vec<usize> {
+    blocks.sort_unstable();
+    blocks.dedup();
+    blocks
+}
+
+/// Skips the broken block starting at `input`: its doc lines and everything
+/// up to the next line starting a doc block.
+fn skip_block(input: &str) -> &str {
+    let mut in_doc = true;
+    let mut offset = 0;
+    for line in input.split_inclusive('\n') {
+        if scan::starts_doc_comment(line.trim_start()) {
+            if !in_doc {
+                return &input[offset..];
+            }
+        } else {
+            in_doc = false;
+        }
+        offset += line.len();
+    }
+    ""
+}
+
+/// Best effort lookup of the symbol documented by a (broken) block.
+fn find_symbol_name(block: &str) -> Option<String> {
+    let decl = block
+        .find(|c: char| !c.is_whitespace())
+        .map(|i| &block[i..])?;
+    let decl = decl
+        .split_inclusive('\n')
+        .find(|line| !line.trim_start().starts_with("///"))
+        .map(|line| &decl[decl.offset(line)..])?;
+    let (_, name) = alt((
+        preceded(
+            tuple((
+                alt((
+                    tag_no_case("func"),
+                    tag_no_case("const"),
+                    tag_no_case("var"),
+                )),
+                multispace1,
+                identifier,
+                multispace1,
+            )),
+            identifier,
+        ),
+        preceded(
+            pair(
+                alt((
+                    tag_no_case("class"),
+                    tag_no_case("prototype"),
+                    tag_no_case("instance"),
+                )),
+                multispace1,
+            ),
+            identifier,
+        ),
+    ))(decl.trim_start())
+    .ok()?;
+    Some(name.to_string())
+}
+
+/// Parses all doc blocks in `input`, a whole script: ordinary comments and
+/// undocumented code between the blocks are skipped, every block documents
+/// the declaration right after it. Stops at the first malformed block unless
+/// `recover` is set, in which case malformed blocks are skipped and every
+/// error is returned. Warnings are returned in both cases.
+pub fn parse_doc_comments(input: &str, recover: bool) -> (Vec<DocuComment>, Vec<ParseError>) {
+    let mut comments = vec![];
+    let mut errors = vec![];
+    let mut parsed = 0;
+    let mut blocks = sorted(scan::doc_blocks(input)).into_iter().peekable();
+    while let Some(start) = blocks.next() {
+        if start < parsed {
+            continue;
+        }
+        let until = blocks.peek().copied().unwrap_or(input.len()).max(start);
+        let block = BlockText::new(input, start, until);
+        match parse_doc_comment(&block.text) {
+            Ok((remaining, (mut comment, warnings))) => {
+                let end = block.input_offset_of(remaining);
+                comment.span = Span {
+                    start,
+                    end,
+                    line: position(input, start).0,
+                    end_line: position(input, end).0,
+                };
+                for tag in &mut comment.tags {
+                    tag.offset = block.input_offset(tag.offset);
+                    (tag.line, tag.column) = position(input, tag.offset);
+                }
+                for (at, message) in warnings {
+                    let at = &input[block.input_offset_of(at)..];
+                    let mut warning = ParseError::new(input, at, message);
+                    warning.func_name = Some(comment.declaration.name().to_string());
+                    warning.severity = Severity::Warning;
+                    errors.push(warning);
+                }
+                comments.push(comment);
+                parsed = end;
+            }
+            Err(nom::Err::Error(e)) | Err(nom::Err::Failure(e)) => {
+                let at = &input[block.input_offset_of(e.input)..];
+                let mut error = ParseError::new(input, at, e.message);
+                error.func_name = find_symbol_name(&block.text);
+                errors.push(error);
+                if !recover {
+                    break;
+                }
+                // unbalanced braces in the broken code throw off the scan,
+                // start over at the next doc block
+                let next = input.len() - skip_block(&input[start..]).len();
+                let rest = scan::doc_blocks(&input[next..]);
+                blocks = sorted(rest.into_iter().map(|block| next + block).collect())
+                    .into_iter()
+                    .peekable();
+            }
+            Err(nom::Err::Incomplete(_)) => unreachable!("complete parsers only"),
+        }
+    }
+    (comments, errors)
+}
+
+/// Renders doc blocks as one Markdown page. Unless [`Options::symbols`] is
+/// set, mentions are linked to the symbols documented on this page.
+pub fn generate_md(comments: &[DocuComment], options: &Options) -> String {
+    render::render(&render::MkDocs, comments, options)
+}
+
+/// Converts all doc blocks in `input` to Markdown, failing on the first
+/// malformed block.
+pub fn parse(input: &str) -> Result<String, ParseError> {
+    parse_with_options(input, &Options::default())
+}
+
+/// Same as [`parse`], with explicit conversion settings.
+pub fn parse_with_options(input: &str, options: &Options) -> Result<String, ParseError> {
+    parse_with_renderer(input, &render::MkDocs, options)
+}
+
+/// Same as [`parse_with_options`], rendering with `renderer` instead of
+/// MkDocs Markdown.
+pub fn parse_with_renderer(
+    input: &str,
+    renderer: &dyn Renderer,
+    options: &Options,
+) -> Result<String, ParseError> {
+    let (comments, errors) = parse_doc_comments(input, false);
+    match errors.into_iter().find(ParseError::is_error) {
+        None => Ok(render::render(renderer, &comments, options)),
+        Some(error) => Err(error),
+    }
+}
+
+/// Converts all doc blocks in `input` to Markdown, skipping malformed blocks.
+/// Returns the Markdown of every block that could be converted together with
+/// the errors of the skipped ones and the warnings.
+pub fn parse_with_recovery(input: &str, options: &Options) -> (String, Vec<ParseError>) {
+    let (comments, errors) = parse_doc_comments(input, true);
+    (generate_md(&comments, options), errors)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn body_with_braces_in_strings_and_comments() {
+        let input = "{ s = \"}\"; // }\n /* } */ if (a) { b(); }; };rest";
+        let (rest, body) = parse_body(input).unwrap();
+        assert_eq!(rest, "rest");
+        assert_eq!(body, &input[..input.len() - ";rest".len()]);
+    }
+
+    #[test]
+    fn comments_before_body() {
+        for input in [
+            "/// C\nfunc void C(var int x) // trailing\n{\n};\n",
+            "/// D\nfunc void D(var int x)\n/* hi */ {};\n",
+        ] {
+            let (comments, errors) = parse_doc_comments(input, false);
+            assert!(errors.is_empty(), "{:?}", errors);
+            assert_eq!(comments.len(), 1);
+        }
+    }
+
+    #[test]
+    fn missing_body_error() {
+        let (_, errors) = parse_doc_comments("/// E\nfunc void E(var int x) return;\n", false);
+        assert_eq!(errors[0].message, "expected `{`");
+        assert_eq!((errors[0].line, errors[0].column), (2, 24));
+    }
+
+    #[test]
+    fn unbalanced_body_is_an_error() {
+        assert!(parse_body("{ if (a) { b(); };").is_err());
+    }
+
+    #[test]
+    fn block_comment_end_after_blocks() {
+        let input =
+            "/** Doc A */\nfunc void A() {};\n/// Doc B\nfunc void B() {};\n// end of section */\n";
+        let (comments, errors) = parse_doc_comments(input, false);
+        assert!(errors.is_empty());
+        let names: Vec<&str> = comments.iter().map(|c| c.declaration.name()).collect();
+        assert_eq!(names, ["A", "B"]);
+    }
+
+    #[test]
+    fn indentation_is_kept() {
+        let input =
+            "/// List:\n/// - one\n///     - nested\n///\n///\n/// End\nfunc void A() {};\n";
+        let (comments, _) = parse_doc_comments(input, false);
+        assert_eq!(
+            comments[0].description.as_deref(),
+            Some("List:\n- one\n    - nested\n\n\nEnd")
+        );
+    }
+
+    #[test]
+    fn error_position_in_javadoc_block() {
+        let input = "/**\n * Shows it\n * @param\n */\nfunc void A(var int x) {};\n";
+        let (_, errors) = parse_doc_comments(input, false);
+        assert_eq!((errors[0].line, errors[0].column), (3, 10));
+    }
+
+    #[test]
+    fn warning_position_in_indented_javadoc_block() {
+        let input =
+            "const int X = 1;\n  /**\n   * Doc\n   * @author me\n   */\nfunc void A() {};\n";
+        let (comments, errors) = parse_doc_comments(input, false);
+        assert_eq!(comments.len(), 1);
+        assert_eq!(errors[0].severity, Severity::Warning);
+        assert_eq!((errors[0].line, errors[0].column), (4, 7));
+    }
+
+    #[test]
+    fn javadoc_block_offsets() {
+        let input = "/**\n * Doc\n */\nfunc void A() {};\n";
+        let block = BlockText::new(input, 0, input.len());
+        assert_eq!(block.text, "/// Doc\n\nfunc void A() {};\n");
+        assert_eq!(block.input_offset(4), input.find("Doc").unwrap());
+        assert_eq!(block.input_offset(9), input.find("func").unwrap());
+    }
+}
@@ -0,0 +1,45 @@
+//! Parser for Daedalus docu comments and Markdown generator for MkDocs
+//! Material based documentation.
+//!
+//! ```
+//! let md = ddcf::parse(
+//!     "/// Display the document\n\
+//!      ///\n\
+//!      /// @param docID document manager ID\n\
+//!      func void Doc_Show(var int docID) {};\n",
+//! )
+//! .unwrap();
+//! assert!(md.starts_with("### `Doc_Show`"));
+//! ```
+//!
+//! [`formatter::parse_doc_comments`] gives access to the parsed
+//! [`DocuComment`]s, which render themselves with
+//! [`DocuComment::generate_md`]. [`project`] resolves Gothic `.src` script
+//! lists to document a whole script tree, [`encoding`] decodes scripts saved
+//! in the legacy Windows code pages, [`links`] links mentions of documented
+//! symbols across pages and [`render`] renders the doc blocks as MkDocs or
+//! plain Markdown, HTML or JSON. [`model`] is the serializable form of the
+//! parsed documentation, see [`parse_to_model`]. [`reformat`] rewrites the doc
+//! comments of a script into a uniform layout and [`skeleton`] adds doc
+//! comment stubs to undocumented functions. [`lint`] checks doc blocks
+//! against the signatures they document, [`coverage`] counts the documented
+//! declarations of a project.
+
+pub mod coverage;
+pub mod encoding;
+pub mod formatter;
+pub mod links;
+pub mod lint;
+pub mod model;
+pub mod project;
+pub mod reformat;
+pub mod render;
+mod scan;
+pub mod skeleton;
+
+pub use formatter::{
+    parse, parse_doc_comments, parse_function_signature, parse_with_options, parse_with_recovery,
+    parse_with_renderer, Declaration, DocuComment, Options, Parameter, ParseError, Severity,
+    Signature, Span, TagKind, TagPosition, TypeKind,
+};
+pub use model::parse_to_model;
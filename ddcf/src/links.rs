@@ -0,0 +1,230 @@
+//! Cross references between documented symbols.
+
+use std::{
+    collections::HashMap,
+    path::{Component, Path, PathBuf},
+};
+
+use crate::formatter::DocuComment;
+
+/// Every documented symbol and the page documenting it, collected before
+/// rendering so mentions can be turned into links.
+#[derive(Debug, Clone, Default)]
+pub struct SymbolIndex {
+    /// Lowercase name to the declared name and its page, `None` for the page
+    /// being rendered.
+    symbols: HashMap<String, (String, Option<PathBuf>)>,
+}
+
+impl SymbolIndex {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the symbols documented by `comments` on `page`, a path relative
+    /// to the output directory. Symbols documented twice keep the first page.
+    pub fn add<'a>(
+        &mut self,
+        comments: impl IntoIterator<Item = &'a DocuComment>,
+        page: Option<&Path>,
+    ) {
+        for comment in comments {
+            let name = comment.declaration.name();
+            self.symbols
+                .entry(name.to_lowercase())
+                .or_insert_with(|| (name.to_string(), page.map(Path::to_path_buf)));
+        }
+    }
+
+    /// Declared name of a symbol, Daedalus names are case-insensitive.
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.symbols
+            .get(&name.to_lowercase())
+            .map(|(name, _)| name.as_str())
+    }
+
+    /// URL of the heading of `name` as seen from the page `from`.
+    pub fn link(&self, name: &str, from: Option<&Path>) -> Option<String> {
+        let (name, page) = self.symbols.get(&name.to_lowercase())?;
+        let page = match (page, from) {
+            (Some(page), Some(from)) if page != from => relative_url(from, page),
+            (Some(page), None) => page.to_string_lossy().replace('\\', "/"),
+            _ => String::new(),
+        };
+        Some(format!("{}#{}", page, anchor(name)))
+    }
+
+    /// Rewrites mentions of documented symbols in `text` into links, except
+    /// for mentions of `this`. Code spans holding just a name are matched
+    /// case-insensitively, plain words only with the declared case. Existing
+    /// links, URLs and code blocks (fenced or indented by four spaces or a
+    /// tab) are left alone.
+    pub fn link_mentions(&self, text: &str, from: Option<&Path>, this: &str) -> String {
+        let mut out = String::with_capacity(text.len());
+        let mut in_fence = false;
+        for line in text.split_inclusive('\n') {
+            let fence = line.trim_start().starts_with("```");
+            in_fence ^= fence;
+            if in_fence || fence || line.starts_with("    ") || line.starts_with('\t') {
+                out.push_str(line);
+            } else {
+                out.push_str(&self.link_line(line, from, this));
+            }
+        }
+        out
+    }
+
+    /// [`SymbolIndex::link_mentions`] for a line outside of code blocks.
+    fn link_line(&self, text: &str, from: Option<&Path>, this: &str) -> String {
+        let linkable = |name: &str, exact: bool| {
+            let declared = self.get(name)?;
+            if declared.eq_ignore_ascii_case(this) || (exact && declared != name) {
+                return None;
+            }
+            self.link(name, from)
+        };
+
+        let mut out = String::with_capacity(text.len());
+        let mut rest = text;
+        while let Some(c) = rest.chars().next() {
+            let len = match c {
+                '`' => match rest[1..].find('`') {
+                    Some(end) => {
+                        let code = &rest[1..end + 1];
+                        if let Some(url) = linkable(code, false) {
+                            out.push_str(&format!("[`{}`]({})", code, url));
+                            rest = &rest[end + 2..];
+                            continue;
+                        }
+                        end + 2
+                    }
+                    None => rest.len(),
+                },
+                '[' => match rest.find("](") {
+                    Some(end) if !rest[..end].contains('\n') => {
+                        rest[end..].find(')').map_or(rest.len(), |i| end + i + 1)
+                    }
+                    _ => 1,
+                },
+                '<' => rest.find('>').map_or(1, |i| i + 1),
+                c if c.is_ascii_alphanumeric() || c == '_' => {
+                    let token = rest.find(char::is_whitespace).map_or(rest, |i| &rest[..i]);
+                    if token.contains("://") {
+                        token.len()
+                    } else {
+                        let word = rest
+                            .find(|c: char| !c.is_ascii_alphanumeric() && c != '_')
+                            .map_or(rest, |i| &rest[..i]);
+                        if !c.is_ascii_digit() {
+                            if let Some(url) = linkable(word, true) {
+                                out.push_str(&format!("[{}]({})", word, url));
+                                rest = &rest[word.len()..];
+                                continue;
+                            }
+                        }
+                        word.len()
+                    }
+                }
+                c => c.len_utf8(),
+            };
+            out.push_str(&rest[..len]);
+            rest = &rest[len..];
+        }
+        out
+    }
+}
+
+/// Anchor MkDocs generates for the heading of a documented symbol.
+pub fn anchor(name: &str) -> String {
+    name.to_lowercase()
+}
+
+/// Anchor MkDocs generates for a heading, e.g. `doc_show` for
+/// `` `Doc_Show` `` or `npc-functions` for `Npc functions`.
+pub fn slug(title: &str) -> String {
+    title
+        .chars()
+        .filter(|&c| c.is_alphanumeric() || matches!(c, '_' | '-' | ' '))
+        .map(|c| {
+            if c == ' ' {
+                '-'
+            } else {
+                c.to_ascii_lowercase()
+            }
+        })
+        .collect()
+}
+
+/// Relative URL of the page `to` from the page `from`.
+fn relative_url(from: &Path, to: &Path) -> String {
+    let names = |path: &Path| -> Vec<String> {
+        path.components()
+            .filter_map(|c| match c {
+                Component::Normal(name) => Some(name.to_string_lossy().into_owned()),
+                _ => None,
+            })
+            .collect()
+    };
+    let from_dir = names(from.parent().unwrap_or(Path::new("")));
+    let to = names(to);
+    let common = from_dir.iter().zip(&to).take_while(|(a, b)| a == b).count();
+    let mut parts = vec!["..".to_string(); from_dir.len() - common];
+    parts.extend_from_slice(&to[common..]);
+    parts.join("/")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::formatter::parse_doc_comments;
+
+    fn index() -> SymbolIndex {
+        let input = "/// Shows\nfunc void Doc_Show() {};\n/// Hides\nfunc void Doc_Hide() {};\n";
+        let mut symbols = SymbolIndex::new();
+        symbols.add(&parse_doc_comments(input, false).0, None);
+        symbols
+    }
+
+    #[test]
+    fn links_words_and_code_spans() {
+        let text = "Like Doc_Hide, `doc_hide` or doc_hide, see [Doc_Hide](x) and Doc_Show";
+        assert_eq!(
+            index().link_mentions(text, None, "Doc_Show"),
+            "Like [Doc_Hide](#doc_hide), [`doc_hide`](#doc_hide) or doc_hide, see [Doc_Hide](x) and Doc_Show"
+        );
+    }
+
+    #[test]
+    fn leaves_urls_alone() {
+        let text = "<https://example.com/Doc_Hide> https://example.com/Doc_Hide";
+        assert_eq!(index().link_mentions(text, None, ""), text);
+    }
+
+    #[test]
+    fn leaves_code_blocks_alone() {
+        let text = "Calls Doc_Hide:\n\n    Doc_Hide(1);\n\tDoc_Hide(2);\n```dae\nDoc_Hide(3);\n```\nDoc_Hide";
+        assert_eq!(
+            index().link_mentions(text, None, ""),
+            "Calls [Doc_Hide](#doc_hide):\n\n    Doc_Hide(1);\n\tDoc_Hide(2);\n```dae\nDoc_Hide(3);\n```\n[Doc_Hide](#doc_hide)"
+        );
+    }
+
+    #[test]
+    fn links_to_other_pages() {
+        let mut symbols = SymbolIndex::new();
+        let (comments, _) = parse_doc_comments("/// Hides\nfunc void Doc_Hide() {};\n", false);
+        symbols.add(&comments, Some(Path::new("doc/hide.md")));
+        assert_eq!(
+            symbols
+                .link("doc_hide", Some(Path::new("npc/npc.md")))
+                .as_deref(),
+            Some("../doc/hide.md#doc_hide")
+        );
+        assert_eq!(
+            symbols
+                .link("Doc_Hide", Some(Path::new("doc/hide.md")))
+                .as_deref(),
+            Some("#doc_hide")
+        );
+    }
+}
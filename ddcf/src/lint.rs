@@ -0,0 +1,147 @@
+//! Checks of doc blocks against the declarations they document.
+//!
+//! ```
+//! let source = "/// Adds\n/// @param b second\nfunc int Add(var int a, var int b) {};\n";
+//! let messages: Vec<String> = ddcf::lint::lint_source(source)
+//!     .iter()
+//!     .map(|lint| lint.message.clone())
+//!     .collect();
+//! assert_eq!(messages, ["parameter `a` is not documented", "missing @return"]);
+//! ```
+
+use crate::formatter::{self, DocuComment, ParseError, Severity, TypeKind};
+
+fn diagnostic(
+    comment: &DocuComment,
+    (line, column): (usize, usize),
+    severity: Severity,
+    message: String,
+) -> ParseError {
+    ParseError {
+        line,
+        column,
+        func_name: Some(comment.declaration.name().to_string()),
+        message,
+        severity,
+    }
+}
+
+/// Checks the `@param` and `@return` tags of a function against its
+/// signature:
+///
+/// - duplicated `@param`s and `@return` on a `void` function are errors,
+/// - undocumented parameters, `@param`s in a different order than the
+///   parameters, a missing `@return` on a non-`void` function and tags
+///   without a description are warnings.
+///
+/// `@param`s which are not in the signature are already rejected by the
+/// parser.
+pub fn lint(comment: &DocuComment) -> Vec<ParseError> {
+    let Some(signature) = comment.declaration.signature() else {
+        return vec![];
+    };
+    let block = (comment.span.line, 1);
+    let tag_at = |name: &str, n: usize| {
+        comment
+            .tags
+            .iter()
+            .filter(|tag| tag.name == name)
+            .nth(n)
+            .map_or(block, |tag| (tag.line, tag.column))
+    };
+
+    let mut lints = vec![];
+    let params = comment.param_desc.as_deref().unwrap_or_default();
+    let mut documented: Vec<&str> = vec![];
+    let is_documented =
+        |documented: &[&str], name: &str| documented.iter().any(|d| d.eq_ignore_ascii_case(name));
+    let mut last_index = None;
+    let mut ordered = true;
+    for (n, (name, desc)) in params.iter().enumerate() {
+        if desc.is_empty() {
+            lints.push(diagnostic(
+                comment,
+                tag_at("param", n),
+                Severity::Warning,
+                format!("@param `{}` has no description", name),
+            ));
+        }
+        if is_documented(&documented, name) {
+            lints.push(diagnostic(
+                comment,
+                tag_at("param", n),
+                Severity::Error,
+                format!("duplicate @param `{}`", name),
+            ));
+            continue;
+        }
+        documented.push(name);
+        let index = signature
+            .params
+            .iter()
+            .position(|p| p.name.eq_ignore_ascii_case(name));
+        if ordered && index < last_index {
+            ordered = false;
+            lints.push(diagnostic(
+                comment,
+                tag_at("param", n),
+                Severity::Warning,
+                format!(
+                    "@param `{}` is out of order, the parameters are {}",
+                    name,
+                    signature
+                        .params
+                        .iter()
+                        .map(|p| format!("`{}`", p.name))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ),
+            ));
+        }
+        last_index = index;
+    }
+    for param in &signature.params {
+        if !is_documented(&documented, &param.name) {
+            lints.push(diagnostic(
+                comment,
+                block,
+                Severity::Warning,
+                format!("parameter `{}` is not documented", param.name),
+            ));
+        }
+    }
+
+    let is_void = TypeKind::of(&signature.return_type) == TypeKind::Void;
+    let has_return = comment.tags.iter().any(|tag| tag.name == "return");
+    match has_return {
+        true if is_void => lints.push(diagnostic(
+            comment,
+            tag_at("return", 0),
+            Severity::Error,
+            "@return on a `void` function".to_string(),
+        )),
+        false if !is_void => lints.push(diagnostic(
+            comment,
+            block,
+            Severity::Warning,
+            "missing @return".to_string(),
+        )),
+        true if comment.ret_stmt.is_none() => lints.push(diagnostic(
+            comment,
+            tag_at("return", 0),
+            Severity::Warning,
+            "@return has no description".to_string(),
+        )),
+        _ => {}
+    }
+    lints
+}
+
+/// Parses `input`, skipping malformed blocks, and lints every block. Returns
+/// the parse errors, warnings and lints ordered by their position.
+pub fn lint_source(input: &str) -> Vec<ParseError> {
+    let (comments, mut diagnostics) = formatter::parse_doc_comments(input, true);
+    diagnostics.extend(comments.iter().flat_map(lint));
+    diagnostics.sort_by_key(|diagnostic| (diagnostic.line, diagnostic.column));
+    diagnostics
+}
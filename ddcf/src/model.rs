@@ -0,0 +1,317 @@
+//! Serializable documentation model, for tools which want the parsed data
+//! instead of rendered pages.
+//!
+//! The JSON form of a [`Document`] is versioned by [`SCHEMA_VERSION`], which
+//! is raised whenever a field is renamed or removed or its meaning changes.
+//! New optional fields may be added without raising it. [`JSON_SCHEMA`]
+//! describes the JSON, a function looks like this:
+//!
+//! ```json
+//! {
+//!   "schema_version": 1,
+//!   "symbols": [
+//!     {
+//!       "kind": "function",
+//!       "name": "Npc_GiveItem",
+//!       "declaration": "func int Npc_GiveItem(var C_Npc npc, var int item)",
+//!       "body": null,
+//!       "signature": {
+//!         "return_type": "int",
+//!         "name": "Npc_GiveItem",
+//!         "params": [
+//!           { "name": "npc", "type": "C_Npc", "is_array": false, "array_len": null, "array_size": null },
+//!           { "name": "item", "type": "int", "is_array": false, "array_len": null, "array_size": null }
+//!         ]
+//!       },
+//!       "description": "Gives an NPC an item.",
+//!       "params": [
+//!         { "name": "npc", "description": "The NPC" },
+//!         { "name": "item", "description": "Instance of the item" }
+//!       ],
+//!       "returns": "The number of items",
+//!       "tags": {
+//!         "see": ["Npc_HasItems"],
+//!         "deprecated": null,
+//!         "since": "1.01",
+//!         "notes": [],
+//!         "warnings": [],
+//!         "examples": [],
+//!         "category": "Items",
+//!         "unknown": []
+//!       },
+//!       "span": { "start": 0, "end": 245, "line": 1, "end_line": 10 }
+//!     }
+//!   ]
+//! }
+//! ```
+//!
+//! ```
+//! let doc = ddcf::parse_to_model("/// Answer\nconst int ANSWER = 42;\n", false);
+//! assert_eq!(doc.symbols[0].name, "ANSWER");
+//! let json = serde_json::to_string(&doc).unwrap();
+//! assert!(json.starts_with("{\"schema_version\":1,"));
+//! ```
+
+use serde::{Deserialize, Serialize};
+
+use crate::formatter::{self, Declaration, DocuComment, ParseError, Signature, Span};
+
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// JSON Schema of a serialized [`Document`], also shipped as
+/// `schema/document.schema.json`.
+pub const JSON_SCHEMA: &str = include_str!("../schema/document.schema.json");
+
+/// All documented symbols of one input, with the errors and warnings of
+/// parsing it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Document {
+    pub schema_version: u32,
+    pub symbols: Vec<Symbol>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub diagnostics: Vec<ParseError>,
+}
+
+impl Document {
+    pub fn new(comments: &[DocuComment], diagnostics: Vec<ParseError>) -> Self {
+        Document {
+            schema_version: SCHEMA_VERSION,
+            symbols: comments.iter().map(Symbol::from).collect(),
+            diagnostics,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SymbolKind {
+    Function,
+    Const,
+    Var,
+    Class,
+    Prototype,
+    Instance,
+}
+
+impl SymbolKind {
+    /// Name of the kind as in the JSON, e.g. `function`.
+    pub fn name(self) -> &'static str {
+        match self {
+            SymbolKind::Function => "function",
+            SymbolKind::Const => "const",
+            SymbolKind::Var => "var",
+            SymbolKind::Class => "class",
+            SymbolKind::Prototype => "prototype",
+            SymbolKind::Instance => "instance",
+        }
+    }
+}
+
+/// A documented symbol.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Symbol {
+    pub kind: SymbolKind,
+    pub name: String,
+    /// Source of the declaration as written. Functions, prototypes and
+    /// instances stop before their body, constants include their value and
+    /// classes their member list.
+    pub declaration: String,
+    /// `{ ... }` body of a function, prototype or instance. The JSON renderer
+    /// only keeps it with [`formatter::Options::show_bodies`].
+    pub body: Option<String>,
+    /// Set for functions only.
+    pub signature: Option<Signature>,
+    /// Type of a constant or variable.
+    #[serde(rename = "type", default, skip_serializing_if = "Option::is_none")]
+    pub ty: Option<String>,
+    /// Size of a constant or variable array, a number or a constant name.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub array_size: Option<String>,
+    pub description: Option<String>,
+    pub params: Vec<ParamDoc>,
+    /// `@return` description.
+    pub returns: Option<String>,
+    pub tags: Tags,
+    pub span: Span,
+}
+
+/// A `@param` tag.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ParamDoc {
+    pub name: String,
+    pub description: String,
+}
+
+/// The tags besides `@param` and `@return`, see [`formatter::TagKind`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Tags {
+    pub see: Vec<String>,
+    /// `@deprecated` reason, empty if none was given.
+    pub deprecated: Option<String>,
+    pub since: Option<String>,
+    pub notes: Vec<String>,
+    pub warnings: Vec<String>,
+    pub examples: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub category: Option<String>,
+    pub unknown: Vec<UnknownTag>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UnknownTag {
+    pub name: String,
+    pub text: String,
+}
+
+impl From<&DocuComment> for Symbol {
+    fn from(comment: &DocuComment) -> Self {
+        let kind = match comment.declaration {
+            Declaration::Func(_) => SymbolKind::Function,
+            Declaration::Const { .. } => SymbolKind::Const,
+            Declaration::Var { .. } => SymbolKind::Var,
+            Declaration::Class { .. } => SymbolKind::Class,
+            Declaration::Prototype { .. } => SymbolKind::Prototype,
+            Declaration::Instance { .. } => SymbolKind::Instance,
+        };
+        let global_type = comment.declaration.global_type();
+        Symbol {
+            kind,
+            name: comment.declaration.name().to_string(),
+            declaration: comment.decl_string.clone(),
+            body: comment.body.clone(),
+            signature: comment.declaration.signature().cloned(),
+            ty: global_type.map(|(ty, _)| ty.to_string()),
+            array_size: global_type.and_then(|(_, size)| size).map(str::to_string),
+            description: comment.description.clone(),
+            params: comment
+                .param_desc
+                .iter()
+                .flatten()
+                .map(|(name, description)| ParamDoc {
+                    name: name.clone(),
+                    description: description.clone(),
+                })
+                .collect(),
+            returns: comment.ret_stmt.clone(),
+            tags: Tags {
+                see: comment.see.clone(),
+                deprecated: comment.deprecated.clone(),
+                since: comment.since.clone(),
+                notes: comment.notes.clone(),
+                warnings: comment.warnings.clone(),
+                examples: comment.examples.clone(),
+                category: comment.category.clone(),
+                unknown: comment
+                    .unknown_tags
+                    .iter()
+                    .map(|(name, text)| UnknownTag {
+                        name: name.clone(),
+                        text: text.clone(),
+                    })
+                    .collect(),
+            },
+            span: comment.span,
+        }
+    }
+}
+
+/// Parses `input` into a [`Document`]. See
+/// [`formatter::parse_doc_comments`] for `recover`, the errors end up in
+/// [`Document::diagnostics`].
+pub fn parse_to_model(input: &str, recover: bool) -> Document {
+    let (comments, errors) = formatter::parse_doc_comments(input, recover);
+    Document::new(&comments, errors)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{json, Value};
+
+    use super::*;
+
+    /// Checks the object keys of `value` against `schema`: every key is a
+    /// known property and every required one is there.
+    fn check(root: &Value, schema: &Value, value: &Value, path: &str) {
+        if let Some(reference) = schema["$ref"].as_str() {
+            let name = reference.trim_start_matches("#/$defs/");
+            return check(root, &root["$defs"][name], value, path);
+        }
+        if let Some(schemas) = schema["oneOf"].as_array() {
+            let is_null = |schema: &Value| schema["type"] == "null";
+            let schema = schemas
+                .iter()
+                .find(|schema| is_null(schema) == value.is_null())
+                .unwrap_or_else(|| panic!("{}: no schema for {}", path, value));
+            return check(root, schema, value, path);
+        }
+        match value {
+            Value::Object(object) => {
+                for key in object.keys() {
+                    assert!(
+                        schema["properties"].get(key).is_some(),
+                        "{}.{} is not in the schema",
+                        path,
+                        key
+                    );
+                }
+                for key in schema["required"].as_array().into_iter().flatten() {
+                    let key = key.as_str().unwrap();
+                    assert!(object.contains_key(key), "{}.{} is missing", path, key);
+                }
+                for (key, value) in object {
+                    let path = format!("{}.{}", path, key);
+                    check(root, &schema["properties"][key], value, &path);
+                }
+            }
+            Value::Array(items) => {
+                for (i, item) in items.iter().enumerate() {
+                    check(root, &schema["items"], item, &format!("{}[{}]", path, i));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    #[test]
+    fn json_matches_schema() {
+        let source = "\
+/// Gives an NPC an item.
+/// @param npc The NPC
+/// @param items Items
+/// @return The number of items
+/// @deprecated
+/// @category Items
+/// @custom text
+func int Npc_GiveItem(var C_Npc npc, var int items[MAX]) {
+    return 1;
+};
+
+/// Maximum level.
+const int MAX_LEVEL[2] = {50, 100};
+
+/// An NPC.
+class C_Npc {
+    var int id;
+};
+
+/// Broken
+/// @param b No such parameter
+func void Broken(var int a) {};
+";
+        let doc = parse_to_model(source, true);
+        assert_eq!(doc.symbols.len(), 3);
+        assert_eq!(
+            doc.symbols[2].declaration,
+            "class C_Npc {\n    var int id;\n};"
+        );
+        assert!(!doc.diagnostics.is_empty());
+
+        let schema: Value = serde_json::from_str(JSON_SCHEMA).unwrap();
+        assert_eq!(
+            schema["properties"]["schema_version"]["const"],
+            json!(SCHEMA_VERSION)
+        );
+        let value = serde_json::to_value(&doc).unwrap();
+        check(&schema, &schema, &value, "document");
+    }
+}
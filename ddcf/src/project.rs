@@ -0,0 +1,322 @@
+//! Gothic `.src` script lists.
+//!
+//! A `.src` file lists the `.d` files of a script project, one path per line,
+//! relative to the `.src` file. Paths use backslashes, are matched
+//! case-insensitively, may contain `*`/`?` wildcards and may point to other
+//! `.src` files.
+
+use std::{
+    collections::HashSet,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    encoding::{self, CodePage},
+    formatter::{self, DocuComment, Options, ParseError},
+    links::SymbolIndex,
+    render::{self, GroupBy, MkDocs},
+};
+
+/// A `.d` file referenced by a `.src` file, with its parsed doc blocks.
+#[derive(Debug)]
+pub struct SourceFile {
+    pub path: PathBuf,
+    /// Path relative to the directory of the root `.src` file.
+    pub relative: PathBuf,
+    pub comments: Vec<DocuComment>,
+    /// Errors and warnings of the file.
+    pub errors: Vec<ParseError>,
+}
+
+impl SourceFile {
+    /// Path of the Markdown page of this file, relative to the output
+    /// directory.
+    pub fn page_path(&self) -> PathBuf {
+        self.relative.with_extension("md")
+    }
+
+    /// Renders the page, links are relative to [`SourceFile::page_path`].
+    pub fn generate_md(&self, options: &Options) -> String {
+        let options = Options {
+            page: Some(self.page_path()),
+            ..options.clone()
+        };
+        formatter::generate_md(&self.comments, &options)
+    }
+
+    /// [`SourceFile::generate_md`] with the blocks grouped, see
+    /// [`render::group`].
+    pub fn generate_grouped_md(&self, options: &Options, by: GroupBy, sort: bool) -> String {
+        let options = Options {
+            page: Some(self.page_path()),
+            ..options.clone()
+        };
+        let name = self.relative.to_string_lossy();
+        let groups = render::group([(&*name, &self.comments[..])], by, sort);
+        render::render_grouped(&MkDocs, &groups, &options)
+    }
+}
+
+/// Case-insensitive match of `name` against a pattern with `*` and `?`.
+fn wildcard_match(pattern: &[char], name: &[char]) -> bool {
+    match (pattern.first(), name.first()) {
+        (None, None) => true,
+        (Some('*'), _) => {
+            wildcard_match(&pattern[1..], name)
+                || (!name.is_empty() && wildcard_match(pattern, &name[1..]))
+        }
+        (Some('?'), Some(_)) => wildcard_match(&pattern[1..], &name[1..]),
+        (Some(p), Some(n)) if p.to_lowercase().eq(n.to_lowercase()) => {
+            wildcard_match(&pattern[1..], &name[1..])
+        }
+        _ => false,
+    }
+}
+
+/// Directory the entries of the `.src` file at `src` are relative to.
+fn src_dir(src: &Path) -> &Path {
+    match src.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir,
+        _ => Path::new("."),
+    }
+}
+
+/// Resolves one entry of the `.src` file at `src`, one path component at a
+/// time so every component is matched case-insensitively.
+fn resolve_entry(src: &Path, entry: &str) -> io::Result<Vec<PathBuf>> {
+    let mut paths = vec![src_dir(src).to_path_buf()];
+    for component in entry.split(['\\', '/']).filter(|c| !c.is_empty()) {
+        let mut next = vec![];
+        for path in &paths {
+            if component == "." || component == ".." {
+                next.push(path.join(component));
+                continue;
+            }
+            let Ok(entries) = fs::read_dir(path) else {
+                continue;
+            };
+            let pattern: Vec<char> = component.chars().collect();
+            for dir_entry in entries {
+                let dir_entry = dir_entry?;
+                let name: Vec<char> = dir_entry.file_name().to_string_lossy().chars().collect();
+                if wildcard_match(&pattern, &name) {
+                    next.push(dir_entry.path());
+                }
+            }
+        }
+        next.sort_by_key(|p| p.to_string_lossy().to_lowercase());
+        paths = next;
+    }
+    paths.retain(|p| p.is_file());
+
+    if paths.is_empty() && !entry.contains(['*', '?']) {
+        return Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("`{}` listed in {} not found", entry, src.display()),
+        ));
+    }
+    Ok(paths)
+}
+
+/// Whether `path` is a `.src` file listing other files.
+pub fn is_src(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("src"))
+}
+
+fn resolve_into(
+    src: &Path,
+    files: &mut Vec<PathBuf>,
+    visited: &mut HashSet<PathBuf>,
+) -> io::Result<()> {
+    if !visited.insert(fs::canonicalize(src)?) {
+        return Ok(());
+    }
+    let content = fs::read(src)?;
+    let content = String::from_utf8_lossy(&content);
+    for line in content.lines() {
+        let entry = line.split("//").next().unwrap_or_default().trim();
+        if entry.is_empty() {
+            continue;
+        }
+        for path in resolve_entry(src, entry)? {
+            if is_src(&path) {
+                resolve_into(&path, files, visited)?;
+            } else {
+                files.push(path);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Lists every file referenced by the `.src` file at `src`, in compilation
+/// order, following nested `.src` files.
+pub fn resolve_src(src: &Path) -> io::Result<Vec<PathBuf>> {
+    let mut files = vec![];
+    resolve_into(src, &mut files, &mut HashSet::new())?;
+    Ok(files)
+}
+
+/// Resolves and parses every file of the `.src` file at `src`. See
+/// [`formatter::parse_doc_comments`] for `recover` and [`encoding::decode`]
+/// for `code_page`.
+pub fn load_src(
+    src: &Path,
+    recover: bool,
+    code_page: Option<CodePage>,
+) -> io::Result<Vec<SourceFile>> {
+    let root = src_dir(src);
+    resolve_src(src)?
+        .into_iter()
+        .map(|path| {
+            let content = fs::read(&path)?;
+            let (comments, errors) =
+                formatter::parse_doc_comments(&encoding::decode(&content, code_page), recover);
+            let relative = path
+                .strip_prefix(root)
+                .map(Path::to_path_buf)
+                .unwrap_or_else(|_| path.file_name().map(PathBuf::from).unwrap_or_default());
+            Ok(SourceFile {
+                path,
+                relative,
+                comments,
+                errors,
+            })
+        })
+        .collect()
+}
+
+/// Collects the symbols of all files, to link them across pages with
+/// [`Options::symbols`].
+pub fn symbol_index(files: &[SourceFile]) -> SymbolIndex {
+    let mut symbols = SymbolIndex::new();
+    for file in files {
+        symbols.add(&file.comments, Some(&file.page_path()));
+    }
+    symbols
+}
+
+/// Index page linking the pages of all files with documentation.
+pub fn generate_index(files: &[SourceFile]) -> String {
+    let mut md = String::from("# Index\n\n");
+    for file in files.iter().filter(|f| !f.comments.is_empty()) {
+        let page = file.page_path();
+        md.push_str(&format!(
+            "- [{}]({}) - {} documented symbols\n",
+            file.relative.display(),
+            page.to_string_lossy().replace('\\', "/"),
+            file.comments.len()
+        ));
+    }
+    md
+}
+
+/// MkDocs `nav` listing `index.md` and the page of every documented file.
+pub fn generate_nav(files: &[SourceFile]) -> String {
+    let mut yaml = String::from("nav:\n  - Index: index.md\n");
+    for file in files.iter().filter(|f| !f.comments.is_empty()) {
+        yaml.push_str(&format!(
+            "  - \"{}\": {}\n",
+            file.relative.display().to_string().replace('\\', "/"),
+            file.page_path().to_string_lossy().replace('\\', "/")
+        ));
+    }
+    yaml
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn matches(pattern: &str, name: &str) -> bool {
+        let pattern: Vec<char> = pattern.chars().collect();
+        let name: Vec<char> = name.chars().collect();
+        wildcard_match(&pattern, &name)
+    }
+
+    /// A fresh directory under the system temp directory.
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("ddcf-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn wildcards() {
+        assert!(matches("*.d", "Npc.d"));
+        assert!(matches("*.D", "npc.d"));
+        assert!(matches("B_?.d", "b_1.D"));
+        assert!(matches("*_Gard*.d", "Npc_Gardist.d"));
+        assert!(matches("*", ""));
+        assert!(!matches("*.d", "Npc.src"));
+        assert!(!matches("B_?.d", "B_12.d"));
+        assert!(!matches("?", ""));
+    }
+
+    #[test]
+    fn bare_src_resolves_from_current_dir() {
+        assert_eq!(src_dir(Path::new("Gothic.src")), Path::new("."));
+        assert_eq!(
+            src_dir(Path::new("Content/Gothic.src")),
+            Path::new("Content")
+        );
+    }
+
+    #[test]
+    fn entries_are_case_insensitive() {
+        let dir = temp_dir("entries");
+        fs::create_dir_all(dir.join("Story/NPC")).unwrap();
+        for file in [
+            "Story/NPC/Bau_1.d",
+            "Story/NPC/Bau_2.d",
+            "Story/NPC/Mil_1.d",
+            "Story/Startup.d",
+        ] {
+            fs::write(dir.join(file), "").unwrap();
+        }
+        let src = dir.join("Gothic.src");
+
+        let names = |paths: Vec<PathBuf>| -> Vec<String> {
+            paths
+                .iter()
+                .map(|p| p.file_name().unwrap().to_string_lossy().into_owned())
+                .collect()
+        };
+        assert_eq!(
+            names(resolve_entry(&src, "STORY\\npc\\BAU_*.D").unwrap()),
+            ["Bau_1.d", "Bau_2.d"]
+        );
+        assert_eq!(
+            names(resolve_entry(&src, "story/startup.d").unwrap()),
+            ["Startup.d"]
+        );
+        assert!(resolve_entry(&src, "Story\\Ork_*.d").unwrap().is_empty());
+
+        let error = resolve_entry(&src, "Story\\Missing.d").unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::NotFound);
+        assert!(error.to_string().contains("Gothic.src"), "{}", error);
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn nested_src_files() {
+        let dir = temp_dir("nested");
+        fs::create_dir_all(dir.join("AI")).unwrap();
+        fs::write(dir.join("Startup.d"), "").unwrap();
+        fs::write(dir.join("AI/Ai.d"), "").unwrap();
+        fs::write(dir.join("AI/AI.src"), "Ai.d\n").unwrap();
+        // the nested file is listed twice but only read once
+        fs::write(
+            dir.join("Gothic.src"),
+            "// comment\nai\\ai.src\nSTARTUP.D // trailing comment\nAI\\AI.src\n",
+        )
+        .unwrap();
+
+        let files = resolve_src(&dir.join("Gothic.src")).unwrap();
+        assert_eq!(files, [dir.join("AI/Ai.d"), dir.join("Startup.d")]);
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,254 @@
+//! Rewrites the doc comments of `.d` sources into one layout, leaving all
+//! other code as it is.
+//!
+//! ```
+//! let source = "///Answer\n/// @since 1.0\nconst int ANSWER = 42;\n";
+//! let (formatted, errors) = ddcf::reformat::format_source(source, &Default::default());
+//! assert!(errors.is_empty());
+//! assert_eq!(formatted, "/// Answer\n///\n/// @since 1.0\nconst int ANSWER = 42;\n");
+//! ```
+
+use crate::formatter::{self, DocuComment, ParseError, TagKind};
+
+/// Settings for [`format_source`].
+#[derive(Debug, Clone)]
+pub struct FormatOptions {
+    /// Maximum line length, including the indentation and `/// `. Longer
+    /// lines are wrapped between words, `None` leaves lines as they are.
+    pub width: Option<usize>,
+}
+
+impl Default for FormatOptions {
+    fn default() -> Self {
+        FormatOptions { width: Some(100) }
+    }
+}
+
+/// Splits `line` into lines of at most `width` characters. Words longer than
+/// that get a line of their own, lines never start with a `@` as that would
+/// start a tag. Indented lines, e.g. of nested lists or code, are kept as
+/// they are.
+fn wrap(line: &str, width: Option<usize>) -> Vec<String> {
+    let Some(width) = width.filter(|&width| line.chars().count() > width) else {
+        return vec![line.to_string()];
+    };
+    if line.starts_with(char::is_whitespace) {
+        return vec![line.to_string()];
+    }
+    let mut lines: Vec<String> = vec![];
+    let mut current = String::new();
+    for word in line.split_whitespace() {
+        let fits = current.chars().count() + 1 + word.chars().count() <= width;
+        if !current.is_empty() && !fits && !word.starts_with('@') {
+            lines.push(std::mem::take(&mut current));
+        }
+        if !current.is_empty() {
+            current.push(' ');
+        }
+        current.push_str(word);
+    }
+    lines.push(current);
+    lines
+}
+
+/// Appends the lines of a tag: the first one starts with `head`, the other
+/// ones are indented to line up with its text.
+fn push_tag(lines: &mut Vec<String>, head: &str, text: &str, width: Option<usize>) {
+    let indent = " ".repeat(head.chars().count() + 1);
+    let width = width.map(|width| width.saturating_sub(indent.len()).max(1));
+    let mut first = true;
+    for line in text.lines() {
+        if line.is_empty() {
+            lines.push(String::new());
+            continue;
+        }
+        for line in wrap(line, width) {
+            if first {
+                lines.push(format!("{} {}", head, line));
+                first = false;
+            } else {
+                lines.push(format!("{}{}", indent, line));
+            }
+        }
+    }
+    if first {
+        lines.push(head.to_string());
+    }
+}
+
+/// The text of the doc lines of `comment`, without `///`.
+fn doc_lines(comment: &DocuComment, width: Option<usize>) -> Vec<String> {
+    let mut lines = vec![];
+    if let Some(description) = &comment.description {
+        for line in description.lines() {
+            lines.extend(wrap(line, width));
+        }
+    }
+
+    let mut tags = vec![];
+    let mut params: Vec<&(String, String)> = comment.param_desc.iter().flatten().collect();
+    if let Some(signature) = comment.declaration.signature() {
+        params.sort_by_key(|(name, _)| {
+            signature
+                .params
+                .iter()
+                .position(|p| p.name.eq_ignore_ascii_case(name))
+        });
+    }
+    let name_width = params.iter().map(|(name, _)| name.len()).max().unwrap_or(0);
+    for (name, desc) in params {
+        let head = format!("@param {:<1$}", name, name_width);
+        push_tag(&mut tags, &head, desc, width);
+    }
+    // tags without text have no value in the comment, but are kept bare
+    let written = |kind: TagKind| {
+        comment
+            .tags
+            .iter()
+            .filter(|tag| TagKind::from_name(&tag.name) == Some(kind))
+            .count()
+    };
+    if let Some(ret) = &comment.ret_stmt {
+        push_tag(&mut tags, "@return", ret, width);
+    } else if written(TagKind::Return) > 0 {
+        tags.push("@return".to_string());
+    }
+    if let Some(reason) = &comment.deprecated {
+        push_tag(&mut tags, "@deprecated", reason, width);
+    }
+    if let Some(since) = &comment.since {
+        push_tag(&mut tags, "@since", since, width);
+    }
+    if let Some(category) = &comment.category {
+        push_tag(&mut tags, "@category", category, width);
+    }
+    for target in &comment.see {
+        push_tag(&mut tags, "@see", target, width);
+    }
+    for note in &comment.notes {
+        push_tag(&mut tags, "@note", note, width);
+    }
+    for _ in comment.notes.len()..written(TagKind::Note) {
+        tags.push("@note".to_string());
+    }
+    for warning in &comment.warnings {
+        push_tag(&mut tags, "@warning", warning, width);
+    }
+    for _ in comment.warnings.len()..written(TagKind::Warning) {
+        tags.push("@warning".to_string());
+    }
+    for example in &comment.examples {
+        tags.push("@example".to_string());
+        tags.extend(example.lines().map(str::to_string));
+    }
+    for (name, text) in &comment.unknown_tags {
+        push_tag(&mut tags, &format!("@{}", name), text, width);
+    }
+
+    if !lines.is_empty() && !tags.is_empty() {
+        lines.push(String::new());
+    }
+    lines.extend(tags);
+    lines
+}
+
+/// Rewrites every doc block of `input` that parses:
+///
+/// - `///` is followed by a single space,
+/// - the description is separated from the tags by an empty `///` line,
+/// - tags come in a fixed order, `@param` in the order of the signature with
+///   their descriptions aligned,
+/// - lines longer than [`FormatOptions::width`] are wrapped.
+///
+/// Code outside of doc blocks, `/** */` blocks and blocks which do not parse
+/// stay byte identical, the errors of the latter are returned.
+pub fn format_source(input: &str, options: &FormatOptions) -> (String, Vec<ParseError>) {
+    let (comments, errors) = formatter::parse_doc_comments(input, true);
+    let mut out = String::with_capacity(input.len());
+    let mut copied = 0;
+    for comment in &comments {
+        let start = comment.span.start;
+        let line_start = input[..start].rfind('\n').map_or(0, |i| i + 1);
+        let indent = &input[line_start..start];
+        if !indent.chars().all(char::is_whitespace) || !input[start..].starts_with("///") {
+            continue;
+        }
+        let end = start
+            + input[start..]
+                .split_inclusive('\n')
+                .take_while(|line| line.trim_start().starts_with("///"))
+                .map(str::len)
+                .sum::<usize>();
+        let newline = if input[start..end].ends_with("\r\n") {
+            "\r\n"
+        } else {
+            "\n"
+        };
+
+        let indent_width: usize = indent.chars().map(|c| if c == '\t' { 4 } else { 1 }).sum();
+        let width = options
+            .width
+            .map(|width| width.saturating_sub(indent_width + 4).max(1));
+        out.push_str(&input[copied..line_start]);
+        let mut lines = doc_lines(comment, width);
+        if lines.is_empty() {
+            // a block of empty lines or of tags without text
+            lines.push(String::new());
+        }
+        for line in &lines {
+            out.push_str(indent);
+            if line.is_empty() {
+                out.push_str("///");
+            } else {
+                out.push_str("/// ");
+                out.push_str(line);
+            }
+            out.push_str(newline);
+        }
+        copied = end;
+    }
+    out.push_str(&input[copied..]);
+    (out, errors)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn format(input: &str) -> String {
+        let (formatted, errors) = format_source(input, &FormatOptions::default());
+        assert!(errors.is_empty());
+        formatted
+    }
+
+    #[test]
+    fn empty_block_is_kept() {
+        let input = "\t///\n\tfunc void A() {};\n";
+        assert_eq!(format(input), input);
+    }
+
+    #[test]
+    fn tags_without_text_are_kept() {
+        let input = "\t/// @note\n\tfunc void A() {};\n";
+        assert_eq!(format(input), input);
+        let input = "/// @return\n/// @warning\n/// @note\n/// @note Hint\nfunc int B() {};\n";
+        assert_eq!(
+            format(input),
+            "/// @return\n/// @note Hint\n/// @note\n/// @warning\nfunc int B() {};\n"
+        );
+    }
+
+    #[test]
+    fn indentation_and_empty_lines_are_kept() {
+        let input =
+            "/// List:\n/// - one\n///     - nested\n///\n///\n///     code\nfunc void A() {};\n";
+        assert_eq!(format(input), input);
+    }
+
+    #[test]
+    fn indented_lines_are_not_wrapped() {
+        let input = "/// Code:\n///     a b c d e f\nfunc void A() {};\n";
+        let options = FormatOptions { width: Some(10) };
+        assert_eq!(format_source(input, &options).0, input);
+    }
+}
@@ -0,0 +1,106 @@
+use crate::formatter::{DocuComment, Options};
+
+use super::Renderer;
+
+/// Plain CommonMark (with GitHub tables), for GitHub and other sites
+/// without the MkDocs extensions.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CommonMark;
+
+/// Prefixes every line with `> `, for quoted notes and warnings.
+fn push_quoted(md: &mut String, title: &str, text: &str) {
+    md.push_str(&format!("> **{}**  ", title));
+    for line in text.lines() {
+        md.push_str("\n>");
+        if !line.is_empty() {
+            md.push(' ');
+            md.push_str(line);
+        }
+    }
+    md.push_str("\n\n");
+}
+
+/// Escapes `text` for a single table cell.
+fn table_cell(text: &str) -> String {
+    text.replace('|', "\\|")
+        .lines()
+        .filter(|line| !line.is_empty())
+        .collect::<Vec<&str>>()
+        .join("<br>")
+}
+
+impl Renderer for CommonMark {
+    fn render_comment(&self, comment: &DocuComment, options: &Options) -> String {
+        let mut md = String::with_capacity(50);
+        let name = comment.declaration.name();
+        let link = |text: &str| options.link_mentions(text, name);
+        md.push_str(&format!("### `{}`\n\n", name));
+        md.push_str(&format!("*{}*", comment.declaration.admonition()));
+        if let Some(since) = &comment.since {
+            md.push_str(&format!(" · *since {}*", since));
+        }
+        md.push_str("\n\n");
+        if let Some(reason) = &comment.deprecated {
+            push_quoted(&mut md, "Deprecated", &link(reason));
+        }
+        if let Some(desc) = &comment.description {
+            md.push_str(&link(desc));
+            md.push_str("\n\n");
+        }
+        md.push_str(&format!("```dae\n{}\n```\n", comment.source(options)));
+
+        let signature = comment.declaration.signature();
+        let params: Vec<_> = comment
+            .param_desc
+            .iter()
+            .flatten()
+            .filter_map(|(name, desc)| Some((signature?.param(name)?, desc)))
+            .collect();
+        if !params.is_empty() {
+            md.push_str("\n| Parameter | Type | Description |\n| --- | --- | --- |\n");
+            for (param, desc) in params {
+                let ty = match options.type_url(&param.ty) {
+                    Some(url) => format!("[`{}`]({})", param.type_string(), url),
+                    None => format!("`{}`", param.type_string()),
+                };
+                md.push_str(&format!(
+                    "| `{}` | {} | {} |\n",
+                    param.name,
+                    ty,
+                    table_cell(&link(desc))
+                ));
+            }
+        }
+        if let Some(ret) = &comment.ret_stmt {
+            md.push_str(&format!("\n**Return value**\n\n{}\n", link(ret)));
+        }
+        for example in &comment.examples {
+            md.push_str(&format!("\n**Example**\n\n```dae\n{}\n```\n", example));
+        }
+        for (title, texts) in [("Note", &comment.notes), ("Warning", &comment.warnings)] {
+            for text in texts {
+                md.push('\n');
+                push_quoted(&mut md, title, &link(text));
+                md.pop();
+            }
+        }
+        if !comment.see.is_empty() {
+            md.push_str("\n**See also**\n\n");
+            for target in &comment.see {
+                match options.see_url(target) {
+                    Some(url) if url == *target => md.push_str(&format!("- <{}>\n", url)),
+                    Some(url) => md.push_str(&format!("- [`{}`]({})\n", target, url)),
+                    None => md.push_str(&format!("- `{}`\n", target)),
+                }
+            }
+        }
+        for (name, text) in &comment.unknown_tags {
+            md.push_str(&format!("\n**@{}** {}\n", name, text));
+        }
+        md
+    }
+
+    fn extension(&self) -> &'static str {
+        "md"
+    }
+}
@@ -0,0 +1,257 @@
+use std::{fmt, str::FromStr};
+
+use crate::{coverage, formatter::DocuComment, links};
+
+/// What [`group`] groups doc blocks by.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GroupBy {
+    /// The `@category` (or `@group`) tag.
+    Category,
+    /// The name prefix, e.g. `Npc_`, see [`coverage::prefix`].
+    Prefix,
+    /// The file the blocks come from.
+    File,
+}
+
+impl fmt::Display for GroupBy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            GroupBy::Category => "category",
+            GroupBy::Prefix => "prefix",
+            GroupBy::File => "file",
+        })
+    }
+}
+
+impl FromStr for GroupBy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "category" | "group" => Ok(GroupBy::Category),
+            "prefix" => Ok(GroupBy::Prefix),
+            "file" => Ok(GroupBy::File),
+            _ => Err(format!(
+                "unknown grouping `{}`, expected category, prefix or file",
+                s
+            )),
+        }
+    }
+}
+
+/// Doc blocks rendered under one heading.
+#[derive(Debug, Clone)]
+pub struct Group<'a> {
+    pub title: String,
+    pub comments: Vec<&'a DocuComment>,
+}
+
+/// Title of the group of blocks without a category or prefix.
+const OTHER: &str = "Other";
+
+/// Groups the doc blocks of `files`, each a file name and its blocks.
+/// Categories and files keep the order they first appear in, prefixes are
+/// sorted; blocks without a category or prefix come last under "Other". With
+/// `sort` the blocks of every group are sorted by name.
+pub fn group<'a>(
+    files: impl IntoIterator<Item = (&'a str, &'a [DocuComment])>,
+    by: GroupBy,
+    sort: bool,
+) -> Vec<Group<'a>> {
+    let mut groups: Vec<Group<'a>> = vec![];
+    let mut other = vec![];
+    for (file, comments) in files {
+        for comment in comments {
+            let title = match by {
+                GroupBy::Category => comment.category.as_deref().unwrap_or_default(),
+                GroupBy::Prefix => coverage::prefix(comment.declaration.name()),
+                GroupBy::File => file,
+            };
+            if title.is_empty() {
+                other.push(comment);
+                continue;
+            }
+            // like names, categories and prefixes are case-insensitive
+            match groups
+                .iter_mut()
+                .find(|group| group.title.eq_ignore_ascii_case(title))
+            {
+                Some(group) => group.comments.push(comment),
+                None => groups.push(Group {
+                    title: title.to_string(),
+                    comments: vec![comment],
+                }),
+            }
+        }
+    }
+    if by == GroupBy::Prefix {
+        groups.sort_by_key(|group| group.title.to_lowercase());
+    }
+    if !other.is_empty() {
+        groups.push(Group {
+            title: OTHER.to_string(),
+            comments: other,
+        });
+    }
+    if sort {
+        for group in &mut groups {
+            group
+                .comments
+                .sort_by_key(|comment| comment.declaration.name().to_lowercase());
+        }
+    }
+    groups
+}
+
+/// Markdown table of contents of `groups`: every group with its symbols.
+pub(super) fn contents(groups: &[Group]) -> String {
+    let mut md = String::from("## Contents\n\n");
+    for group in groups {
+        md.push_str(&format!(
+            "- [{}](#{})\n",
+            group.title,
+            links::slug(&group.title)
+        ));
+        for comment in &group.comments {
+            let name = comment.declaration.name();
+            md.push_str(&format!("    - [`{}`](#{})\n", name, links::anchor(name)));
+        }
+    }
+    md
+}
+
+/// `text` as a double-quoted YAML string.
+fn yaml_string(text: &str) -> String {
+    format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// MkDocs `nav` entry for the grouped page `page`, a path relative to the
+/// `docs` directory: a section named `title` linking the page and each group.
+pub fn nav(title: &str, page: &str, groups: &[Group]) -> String {
+    let mut yaml = format!("- {}:\n", yaml_string(title));
+    yaml.push_str(&format!("    - {}: {}\n", yaml_string("Contents"), page));
+    for group in groups {
+        yaml.push_str(&format!(
+            "    - {}: {}#{}\n",
+            yaml_string(&group.title),
+            page,
+            links::slug(&group.title)
+        ));
+    }
+    yaml
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::formatter::parse_doc_comments;
+
+    const NPCS: &str = "\
+/// Spawns an NPC.
+/// @category NPCs
+func void Wld_InsertNpc(var int npc, var string spawn) {};
+
+/// Rain.
+/// @category World
+func void Wld_SetRain() {};
+
+/// Attacks.
+/// @category npcs
+func void npc_Attack() {};
+
+/// Without a category.
+const int MAX = 1;
+";
+
+    const ITEMS: &str = "\
+/// Gives an item.
+/// @category World
+func void Npc_GiveItem() {};
+
+/// Creates an item.
+/// @category Items
+func void CreateInvItem() {};
+";
+
+    fn titles(groups: &[Group]) -> Vec<String> {
+        groups.iter().map(|group| group.title.clone()).collect()
+    }
+
+    fn names(group: &Group) -> Vec<String> {
+        group
+            .comments
+            .iter()
+            .map(|comment| comment.declaration.name().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn by_category() {
+        let (npcs, _) = parse_doc_comments(NPCS, false);
+        let (items, _) = parse_doc_comments(ITEMS, false);
+        let groups = group(
+            [("Npc.d", &npcs[..]), ("Items.d", &items[..])],
+            GroupBy::Category,
+            false,
+        );
+        assert_eq!(titles(&groups), ["NPCs", "World", "Items", "Other"]);
+        assert_eq!(names(&groups[0]), ["Wld_InsertNpc", "npc_Attack"]);
+        assert_eq!(names(&groups[1]), ["Wld_SetRain", "Npc_GiveItem"]);
+        assert_eq!(names(&groups[3]), ["MAX"]);
+    }
+
+    #[test]
+    fn by_prefix_and_sorted() {
+        let (npcs, _) = parse_doc_comments(NPCS, false);
+        let (items, _) = parse_doc_comments(ITEMS, false);
+        let files = [("Npc.d", &npcs[..]), ("Items.d", &items[..])];
+
+        let groups = group(files, GroupBy::Prefix, false);
+        assert_eq!(titles(&groups), ["npc_", "Wld_", "Other"]);
+        assert_eq!(names(&groups[0]), ["npc_Attack", "Npc_GiveItem"]);
+        assert_eq!(names(&groups[2]), ["MAX", "CreateInvItem"]);
+
+        let groups = group(files, GroupBy::Prefix, true);
+        assert_eq!(names(&groups[1]), ["Wld_InsertNpc", "Wld_SetRain"]);
+        assert_eq!(names(&groups[2]), ["CreateInvItem", "MAX"]);
+    }
+
+    #[test]
+    fn by_file() {
+        let (npcs, _) = parse_doc_comments(NPCS, false);
+        let (items, _) = parse_doc_comments(ITEMS, false);
+        let groups = group(
+            [("Npc.d", &npcs[..]), ("Items.d", &items[..])],
+            GroupBy::File,
+            false,
+        );
+        assert_eq!(titles(&groups), ["Npc.d", "Items.d"]);
+    }
+
+    #[test]
+    fn contents_and_nav() {
+        let (items, _) = parse_doc_comments(ITEMS, false);
+        let groups = group([("Items.d", &items[..])], GroupBy::Category, false);
+        assert_eq!(
+            contents(&groups),
+            "## Contents\n\n\
+             - [World](#world)\n    - [`Npc_GiveItem`](#npc_giveitem)\n\
+             - [Items](#items)\n    - [`CreateInvItem`](#createinvitem)\n"
+        );
+        assert_eq!(
+            nav("Items \"Gothic\"", "api/Items.md", &groups),
+            "- \"Items \\\"Gothic\\\"\":\n    \
+             - \"Contents\": api/Items.md\n    \
+             - \"World\": api/Items.md#world\n    \
+             - \"Items\": api/Items.md#items\n"
+        );
+    }
+
+    #[test]
+    fn group_by_names() {
+        assert_eq!("Group".parse(), Ok(GroupBy::Category));
+        assert_eq!("PREFIX".parse(), Ok(GroupBy::Prefix));
+        assert_eq!(GroupBy::File.to_string(), "file");
+        assert!("name".parse::<GroupBy>().is_err());
+    }
+}
@@ -0,0 +1,204 @@
+use crate::{
+    formatter::{DocuComment, Options},
+    links,
+};
+
+use super::{
+    inline::{escape, inline, is_safe_url},
+    Group, Renderer,
+};
+
+/// A standalone HTML page. Descriptions are rendered with the inline
+/// Markdown used in doc comments: code spans, links and emphasis.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Html;
+
+const STYLE: &str = "body { font-family: sans-serif; max-width: 60em; margin: auto; }
+section { border-left: 4px solid #448aff; padding: 0 1em; margin: 1.5em 0; }
+section.deprecated { border-color: #ff9100; }
+pre { background: #f5f5f5; padding: 0.5em; }
+table { border-collapse: collapse; }
+th, td { border: 1px solid #e0e0e0; padding: 0.2em 0.5em; text-align: left; }
+td p { margin: 0; }
+.badge { background: #e0e0e0; border-radius: 0.5em; padding: 0 0.5em; }
+.kind { color: #757575; }
+.admonition { border-left: 4px solid #00b8d4; padding: 0 1em; }
+.admonition.warning { border-color: #ff9100; }
+";
+
+/// The head of a page, up to the opening `<body>`.
+fn page_start() -> String {
+    format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>Documentation</title>\n<style>\n{}</style>\n</head>\n<body>\n",
+        STYLE
+    )
+}
+
+/// Paragraphs separated by empty lines become `<p>` elements.
+fn paragraphs(text: &str) -> String {
+    text.split("\n\n")
+        .filter(|paragraph| !paragraph.trim().is_empty())
+        .map(|paragraph| format!("<p>{}</p>\n", inline(paragraph)))
+        .collect()
+}
+
+impl Renderer for Html {
+    fn render_comment(&self, comment: &DocuComment, options: &Options) -> String {
+        let mut html = String::with_capacity(200);
+        let name = comment.declaration.name();
+        let kind = comment.declaration.admonition();
+        let link = |text: &str| options.link_mentions(text, name);
+        let class = if comment.deprecated.is_some() {
+            format!("{} deprecated", kind)
+        } else {
+            kind.to_string()
+        };
+        html.push_str(&format!("<section class=\"{}\">\n", class));
+        html.push_str(&format!(
+            "<h3 id=\"{}\"><code>{}</code> <span class=\"kind\">{}</span></h3>\n",
+            links::anchor(name),
+            escape(name),
+            kind
+        ));
+        if let Some(since) = &comment.since {
+            html.push_str(&format!(
+                "<span class=\"badge since\">Since {}</span>\n",
+                escape(since)
+            ));
+        }
+        if let Some(reason) = &comment.deprecated {
+            html.push_str("<div class=\"admonition warning\"><p><strong>Deprecated</strong></p>\n");
+            if !reason.is_empty() {
+                html.push_str(&paragraphs(&link(reason)));
+            }
+            html.push_str("</div>\n");
+        }
+        if let Some(desc) = &comment.description {
+            html.push_str(&paragraphs(&link(desc)));
+        }
+        html.push_str(&format!(
+            "<pre><code class=\"language-dae\">{}</code></pre>\n",
+            escape(&comment.source(options))
+        ));
+
+        let signature = comment.declaration.signature();
+        let params: Vec<_> = comment
+            .param_desc
+            .iter()
+            .flatten()
+            .filter_map(|(name, desc)| Some((signature?.param(name)?, desc)))
+            .collect();
+        if !params.is_empty() {
+            html.push_str("<h4>Parameters</h4>\n<table>\n");
+            html.push_str("<tr><th>Parameter</th><th>Type</th><th>Description</th></tr>\n");
+            for (param, desc) in params {
+                let ty = format!("<code>{}</code>", escape(&param.type_string()));
+                let ty = match options.type_url(&param.ty) {
+                    Some(url) => format!("<a href=\"{}\">{}</a>", escape(&url), ty),
+                    None => ty,
+                };
+                html.push_str(&format!(
+                    "<tr><td><code>{}</code></td><td>{}</td><td>{}</td></tr>\n",
+                    escape(&param.name),
+                    ty,
+                    paragraphs(&link(desc)).trim_end()
+                ));
+            }
+            html.push_str("</table>\n");
+        }
+        if let Some(ret) = &comment.ret_stmt {
+            html.push_str("<h4>Return value</h4>\n");
+            html.push_str(&paragraphs(&link(ret)));
+        }
+        for example in &comment.examples {
+            html.push_str(&format!(
+                "<h4>Example</h4>\n<pre><code class=\"language-dae\">{}</code></pre>\n",
+                escape(example)
+            ));
+        }
+        for (kind, texts) in [("note", &comment.notes), ("warning", &comment.warnings)] {
+            for text in texts {
+                html.push_str(&format!(
+                    "<div class=\"admonition {}\">\n{}</div>\n",
+                    kind,
+                    paragraphs(&link(text))
+                ));
+            }
+        }
+        if !comment.see.is_empty() {
+            html.push_str("<h4>See also</h4>\n<ul>\n");
+            for target in &comment.see {
+                let label = if target.contains("://") {
+                    escape(target)
+                } else {
+                    format!("<code>{}</code>", escape(target))
+                };
+                match options.see_url(target).filter(|url| is_safe_url(url)) {
+                    Some(url) => html.push_str(&format!(
+                        "<li><a href=\"{}\">{}</a></li>\n",
+                        escape(&url),
+                        label
+                    )),
+                    None => html.push_str(&format!("<li>{}</li>\n", label)),
+                }
+            }
+            html.push_str("</ul>\n");
+        }
+        for (name, text) in &comment.unknown_tags {
+            html.push_str(&format!(
+                "<p><strong>@{}</strong> {}</p>\n",
+                escape(name),
+                inline(text)
+            ));
+        }
+        html.push_str("</section>\n");
+        html
+    }
+
+    fn render_page(&self, comments: &[DocuComment], options: &Options) -> String {
+        let mut html = page_start();
+        for comment in comments {
+            html.push_str(&self.render_comment(comment, options));
+        }
+        html.push_str("</body>\n</html>\n");
+        html
+    }
+
+    fn render_groups(&self, groups: &[Group], options: &Options) -> String {
+        let mut html = page_start();
+        html.push_str("<nav>\n<h2>Contents</h2>\n<ul>\n");
+        for group in groups {
+            html.push_str(&format!(
+                "<li><a href=\"#{}\">{}</a>\n<ul>\n",
+                escape(&links::slug(&group.title)),
+                escape(&group.title)
+            ));
+            for comment in &group.comments {
+                let name = comment.declaration.name();
+                html.push_str(&format!(
+                    "<li><a href=\"#{}\"><code>{}</code></a></li>\n",
+                    escape(&links::anchor(name)),
+                    escape(name)
+                ));
+            }
+            html.push_str("</ul>\n</li>\n");
+        }
+        html.push_str("</ul>\n</nav>\n");
+        for group in groups {
+            html.push_str(&format!(
+                "<h2 id=\"{}\">{}</h2>\n",
+                escape(&links::slug(&group.title)),
+                escape(&group.title)
+            ));
+            for comment in &group.comments {
+                html.push_str(&self.render_comment(comment, options));
+            }
+        }
+        html.push_str("</body>\n</html>\n");
+        html
+    }
+
+    fn extension(&self) -> &'static str {
+        "html"
+    }
+}
@@ -0,0 +1,164 @@
+//! Inline Markdown of descriptions as HTML, for the [`Html`](super::Html)
+//! renderer and the [`preview`](super::preview).
+
+/// The tags of the raw HTML the renderers write.
+const SPANS: [&str; 2] = ["<span class=\"badge since\">", "</span>"];
+
+/// Escapes `text` for HTML text and attribute values.
+pub(super) fn escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+/// `[label](url)` at the start of `text`, with the length of the link.
+fn markdown_link(text: &str) -> Option<(&str, &str, usize)> {
+    let close = text.find(']')?;
+    if text[..close].contains('\n') || !text[close + 1..].starts_with('(') {
+        return None;
+    }
+    let end = close + 2 + text[close + 2..].find(')')?;
+    Some((&text[1..close], &text[close + 2..end], end + 1))
+}
+
+/// Whether `url` may be linked: `http(s)`, relative or an anchor, but not
+/// e.g. `javascript:`.
+pub(super) fn is_safe_url(url: &str) -> bool {
+    let url = url.trim();
+    match url.find([':', '/', '?', '#']) {
+        Some(i) if url[i..].starts_with(':') => {
+            let scheme = &url[..i];
+            scheme.eq_ignore_ascii_case("http") || scheme.eq_ignore_ascii_case("https")
+        }
+        _ => true,
+    }
+}
+
+/// Converts the inline Markdown of a line: code spans (with a `#!language`),
+/// links, `<URL>`s, emphasis, escapes and entities. Raw HTML is escaped,
+/// except for the [`SPANS`].
+pub(super) fn inline(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(c) = rest.chars().next() {
+        let (html, len) = match c {
+            '`' => match rest[1..].find('`') {
+                Some(end) => {
+                    let code = &rest[1..end + 1];
+                    let html = match code.strip_prefix("#!") {
+                        Some(code) => {
+                            let (language, code) = code.split_once(' ').unwrap_or((code, ""));
+                            format!(
+                                "<code class=\"language-{}\">{}</code>",
+                                escape(language),
+                                escape(code)
+                            )
+                        }
+                        None => format!("<code>{}</code>", escape(code)),
+                    };
+                    (html, end + 2)
+                }
+                None => (escape("`"), 1),
+            },
+            '[' => match markdown_link(rest) {
+                Some((label, url, len)) if is_safe_url(url) => (
+                    format!("<a href=\"{}\">{}</a>", escape(url), inline(label)),
+                    len,
+                ),
+                // other links are shown as written
+                Some((_, _, len)) => (escape(&rest[..len]), len),
+                None => (escape("["), 1),
+            },
+            '<' => match rest.find('>') {
+                Some(end) if rest[1..end].contains("://") && is_safe_url(&rest[1..end]) => {
+                    let url = escape(&rest[1..end]);
+                    (format!("<a href=\"{}\">{}</a>", url, url), end + 1)
+                }
+                // the only raw HTML the renderers write, any other is escaped
+                Some(end) if SPANS.contains(&&rest[..=end]) => (rest[..=end].to_string(), end + 1),
+                _ => (escape("<"), 1),
+            },
+            // entities like `&lt;` in the raw HTML
+            '&' => match rest.find(';') {
+                Some(end)
+                    if end > 1
+                        && rest[1..end]
+                            .chars()
+                            .all(|c| c.is_ascii_alphanumeric() || c == '#') =>
+                {
+                    (rest[..=end].to_string(), end + 1)
+                }
+                _ => (escape("&"), 1),
+            },
+            '*' => {
+                let marker = if rest.starts_with("**") { "**" } else { "*" };
+                let tag = if marker == "**" { "strong" } else { "em" };
+                let after = &rest[marker.len()..];
+                match after.find(marker) {
+                    Some(end) if end > 0 && !after.starts_with(char::is_whitespace) => (
+                        format!("<{}>{}</{}>", tag, inline(&after[..end]), tag),
+                        end + 2 * marker.len(),
+                    ),
+                    _ => (escape(marker), marker.len()),
+                }
+            }
+            '\\' if rest[1..].starts_with(|c: char| c.is_ascii_punctuation()) => {
+                (escape(&rest[1..2]), 2)
+            }
+            c => (escape(&c.to_string()), c.len_utf8()),
+        };
+        out.push_str(&html);
+        rest = &rest[len..];
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn safe_urls() {
+        for url in [
+            "https://example.com",
+            "HTTP://example.com",
+            "doc.md#a",
+            "#a",
+            "../a.md",
+        ] {
+            assert!(is_safe_url(url), "{}", url);
+        }
+        for url in [
+            "javascript:alert(1)",
+            " JavaScript:x",
+            "java\tscript:x",
+            "data:text/html,x",
+        ] {
+            assert!(!is_safe_url(url), "{}", url);
+        }
+    }
+
+    #[test]
+    fn unsafe_links_are_text() {
+        assert_eq!(
+            inline("[me](javascript:alert(1)) <javascript://x> [ok](#a)"),
+            "[me](javascript:alert(1)) &lt;javascript://x&gt; <a href=\"#a\">ok</a>"
+        );
+    }
+
+    #[test]
+    fn raw_html_is_escaped() {
+        assert_eq!(
+            inline("<span onclick=\"x\"><span class=\"badge since\">1</span> &lt; *a*"),
+            "&lt;span onclick=&quot;x&quot;&gt;<span class=\"badge since\">1</span> &lt; <em>a</em>"
+        );
+    }
+}
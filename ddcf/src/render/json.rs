@@ -0,0 +1,58 @@
+use serde::Serialize;
+
+use crate::{
+    formatter::{DocuComment, Options},
+    model::{Document, Symbol},
+};
+
+use super::{Group, Renderer};
+
+/// JSON of the [`crate::model`]: a page is a [`Document`], a single block a
+/// [`Symbol`]. Texts are kept as written, mentions are not turned into links.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Json;
+
+fn symbol(comment: &DocuComment, options: &Options) -> Symbol {
+    let mut symbol = Symbol::from(comment);
+    if !options.show_bodies {
+        symbol.body = None;
+    }
+    symbol
+}
+
+fn to_json(value: &impl Serialize) -> String {
+    let mut json = serde_json::to_string_pretty(value).expect("the model always serializes");
+    json.push('\n');
+    json
+}
+
+impl Renderer for Json {
+    fn render_comment(&self, comment: &DocuComment, options: &Options) -> String {
+        to_json(&symbol(comment, options))
+    }
+
+    fn render_page(&self, comments: &[DocuComment], options: &Options) -> String {
+        let mut document = Document::new(&[], vec![]);
+        document.symbols = comments
+            .iter()
+            .map(|comment| symbol(comment, options))
+            .collect();
+        to_json(&document)
+    }
+
+    /// The symbols in the order of the groups, their categories are in the
+    /// tags.
+    fn render_groups(&self, groups: &[Group], options: &Options) -> String {
+        let mut document = Document::new(&[], vec![]);
+        document.symbols = groups
+            .iter()
+            .flat_map(|group| &group.comments)
+            .map(|comment| symbol(comment, options))
+            .collect();
+        to_json(&document)
+    }
+
+    fn extension(&self) -> &'static str {
+        "json"
+    }
+}
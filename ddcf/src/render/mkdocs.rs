@@ -0,0 +1,118 @@
+use crate::formatter::{DocuComment, Options};
+
+use super::{inline::escape, Renderer};
+
+/// Markdown for MkDocs Material: every symbol is an admonition of its kind,
+/// code is highlighted as `dae`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MkDocs;
+
+/// Appends `text` indented into an admonition, keeping empty lines empty.
+fn push_indented(md: &mut String, text: &str) {
+    push_indented_by(md, text, "\t");
+}
+
+fn push_indented_by(md: &mut String, text: &str, indent: &str) {
+    for line in text.lines() {
+        if line.is_empty() {
+            md.push('\n');
+        } else {
+            md.push_str(&format!("{}{}\n", indent, line));
+        }
+    }
+}
+
+impl Renderer for MkDocs {
+    fn render_comment(&self, comment: &DocuComment, options: &Options) -> String {
+        let mut md = String::with_capacity(50);
+        let name = comment.declaration.name();
+        let link = |text: &str| options.link_mentions(text, name);
+        md.push_str(&format!("### `{}`\n", name));
+        md.push_str(&format!(
+            "!!! {} \"`{}`\"\n",
+            comment.declaration.admonition(),
+            name
+        ));
+        if let Some(reason) = &comment.deprecated {
+            md.push_str("\t!!! warning \"Deprecated\"\n");
+            push_indented_by(&mut md, &link(reason), "\t\t");
+            md.push('\n');
+        }
+        if let Some(since) = &comment.since {
+            md.push_str(&format!(
+                "\t<span class=\"badge since\">Since {}</span>\n\n",
+                escape(since)
+            ));
+        }
+        if let Some(desc) = &comment.description {
+            push_indented(&mut md, &link(desc));
+        }
+        md.push_str("\t```dae\n");
+        for line in comment.source(options).lines() {
+            md.push_str(&format!("\t{}\n", line));
+        }
+        md.push_str("\t```\n");
+        if let Some(params) = &comment.param_desc {
+            if !params.is_empty() {
+                md.push_str("\n\t**Parameters**  \n");
+            }
+            for (name, desc) in params {
+                let signature = comment.declaration.signature();
+                if let Some(param) = signature.and_then(|s| s.param(name)) {
+                    let desc = link(desc);
+                    let mut lines = desc.lines();
+                    let ty = options
+                        .type_url(&param.ty)
+                        .map(|url| format!(" ([`{}`]({}))", param.ty, url))
+                        .unwrap_or_default();
+                    md.push_str(&format!(
+                        "\t- `#!dae {}`{} - {}\n",
+                        param,
+                        ty,
+                        lines.next().unwrap_or_default()
+                    ));
+                    for line in lines {
+                        if line.is_empty() {
+                            md.push('\n');
+                        } else {
+                            md.push_str(&format!("\t  {}\n", line));
+                        }
+                    }
+                }
+            }
+        }
+        if let Some(ret) = &comment.ret_stmt {
+            md.push_str("\n\t**Return value**  \n");
+            push_indented(&mut md, &link(ret));
+        }
+        for example in &comment.examples {
+            md.push_str("\n\t**Example**\n\t```dae\n");
+            push_indented(&mut md, example);
+            md.push_str("\t```\n");
+        }
+        for (kind, texts) in [("note", &comment.notes), ("warning", &comment.warnings)] {
+            for text in texts {
+                md.push_str(&format!("\n\t!!! {}\n", kind));
+                push_indented_by(&mut md, &link(text), "\t\t");
+            }
+        }
+        if !comment.see.is_empty() {
+            md.push_str("\n\t**See also**  \n");
+            for target in &comment.see {
+                match options.see_url(target) {
+                    Some(url) if url == *target => md.push_str(&format!("\t- <{}>\n", url)),
+                    Some(url) => md.push_str(&format!("\t- [`{}`]({})\n", target, url)),
+                    None => md.push_str(&format!("\t- `{}`\n", target)),
+                }
+            }
+        }
+        for (name, text) in &comment.unknown_tags {
+            md.push_str(&format!("\n\t**@{}** {}\n", name, text));
+        }
+        md
+    }
+
+    fn extension(&self) -> &'static str {
+        "md"
+    }
+}
@@ -0,0 +1,149 @@
+//! Output formats for parsed doc blocks.
+//!
+//! ```
+//! use ddcf::render::{self, Format};
+//!
+//! let (comments, _) = ddcf::parse_doc_comments("/// Answer\nconst int ANSWER = 42;\n", false);
+//! let html = render::render(Format::Html.renderer(), &comments, &Default::default());
+//! assert!(html.contains("<h3 id=\"answer\">"));
+//! ```
+//!
+//! Pages can be grouped by category, name prefix or file, under a table of
+//! contents:
+//!
+//! ```
+//! use ddcf::render::{self, Format, GroupBy};
+//!
+//! let source = "/// Answer\n/// @category Numbers\nconst int ANSWER = 42;\n";
+//! let (comments, _) = ddcf::parse_doc_comments(source, false);
+//! let groups = render::group([("Answer.d", &comments[..])], GroupBy::Category, false);
+//! let md = render::render_grouped(Format::MkDocs.renderer(), &groups, &Default::default());
+//! assert!(md.starts_with("## Contents\n\n- [Numbers](#numbers)\n    - [`ANSWER`](#answer)\n"));
+//! ```
+
+use std::{fmt, str::FromStr};
+
+use crate::formatter::{DocuComment, Options};
+
+mod commonmark;
+mod group;
+mod html;
+mod inline;
+mod json;
+mod mkdocs;
+pub mod preview;
+
+pub use commonmark::CommonMark;
+pub use group::{group, nav, Group, GroupBy};
+pub use html::Html;
+pub use json::Json;
+pub use mkdocs::MkDocs;
+
+/// Turns the doc blocks of a page into one output document.
+pub trait Renderer {
+    /// Renders one doc block.
+    fn render_comment(&self, comment: &DocuComment, options: &Options) -> String;
+
+    /// Renders a page from its doc blocks, by default the blocks separated by
+    /// an empty line.
+    fn render_page(&self, comments: &[DocuComment], options: &Options) -> String {
+        comments
+            .iter()
+            .map(|comment| self.render_comment(comment, options))
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+
+    /// Renders a page of titled groups, by default a table of contents
+    /// followed by a `##` heading per group over its blocks.
+    fn render_groups(&self, groups: &[Group], options: &Options) -> String {
+        let mut page = group::contents(groups);
+        for group in groups {
+            page.push_str(&format!("\n## {}\n\n", group.title));
+            let comments: Vec<String> = group
+                .comments
+                .iter()
+                .map(|comment| self.render_comment(comment, options))
+                .collect();
+            page.push_str(&comments.join("\n"));
+        }
+        page
+    }
+
+    /// File extension of the rendered pages.
+    fn extension(&self) -> &'static str;
+}
+
+/// Renders `comments` as one page. Unless [`Options::symbols`] is set,
+/// mentions are linked to the symbols documented on this page.
+pub fn render(renderer: &dyn Renderer, comments: &[DocuComment], options: &Options) -> String {
+    renderer.render_page(comments, &options.for_page(comments))
+}
+
+/// Renders `groups` as one page, see [`render`].
+pub fn render_grouped(renderer: &dyn Renderer, groups: &[Group], options: &Options) -> String {
+    let comments = groups
+        .iter()
+        .flat_map(|group| group.comments.iter().copied());
+    renderer.render_groups(groups, &options.for_page(comments))
+}
+
+/// The built-in renderers, for selecting one by name.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Format {
+    /// MkDocs Material admonitions, see [`MkDocs`].
+    #[default]
+    MkDocs,
+    /// Plain CommonMark with tables, see [`CommonMark`].
+    CommonMark,
+    /// A standalone HTML page, see [`Html`].
+    Html,
+    /// JSON, see [`Json`].
+    Json,
+}
+
+impl Format {
+    pub const ALL: [Format; 4] = [
+        Format::MkDocs,
+        Format::CommonMark,
+        Format::Html,
+        Format::Json,
+    ];
+
+    pub fn renderer(self) -> &'static dyn Renderer {
+        match self {
+            Format::MkDocs => &MkDocs,
+            Format::CommonMark => &CommonMark,
+            Format::Html => &Html,
+            Format::Json => &Json,
+        }
+    }
+}
+
+impl fmt::Display for Format {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Format::MkDocs => "mkdocs",
+            Format::CommonMark => "commonmark",
+            Format::Html => "html",
+            Format::Json => "json",
+        })
+    }
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "mkdocs" => Ok(Format::MkDocs),
+            "commonmark" | "markdown" | "md" | "gfm" => Ok(Format::CommonMark),
+            "html" => Ok(Format::Html),
+            "json" => Ok(Format::Json),
+            _ => Err(format!(
+                "unknown format `{}`, expected mkdocs, commonmark, html or json",
+                s
+            )),
+        }
+    }
+}
@@ -0,0 +1,227 @@
+//! HTML preview of the Markdown renderers' output, close to what MkDocs
+//! Material shows. Only the Markdown the renderers write is understood:
+//! headings, paragraphs, lists, fenced code, block quotes, tables and
+//! admonitions, with code spans (including `#!dae` ones), links, emphasis and
+//! the `<span>` of the since badge inline.
+//!
+//! ```
+//! let html = ddcf::render::preview::to_html("!!! note \"Hint\"\n\tUse `#!dae Doc_Show`\n");
+//! assert_eq!(
+//!     html,
+//!     "<div class=\"admonition note\">\n<p class=\"admonition-title\">Hint</p>\n\
+//!      <p>Use <code class=\"language-dae\">Doc_Show</code></p>\n</div>\n"
+//! );
+//! ```
+
+use crate::links::slug;
+
+use super::inline::{escape, inline};
+
+/// Style sheet for the HTML of [`to_html`].
+pub const STYLE: &str = "body { font-family: sans-serif; margin: 0 1em; }
+pre { background: #f5f5f5; padding: 0.5em; }
+table { border-collapse: collapse; }
+th, td { border: 1px solid #e0e0e0; padding: 0.2em 0.5em; }
+blockquote { border-left: 4px solid #e0e0e0; margin: 0; padding: 0 1em; }
+.badge { background: #e0e0e0; border-radius: 0.5em; padding: 0 0.5em; }
+.admonition { border-left: 4px solid #448aff; margin: 1em 0; padding: 0 1em; }
+.admonition-title { font-weight: bold; }
+.admonition.note { border-color: #00b8d4; }
+.admonition.warning { border-color: #ff9100; }
+";
+
+/// Content of an indented (by a tab or four spaces) line.
+fn dedent(line: &str) -> Option<&str> {
+    line.strip_prefix('\t')
+        .or_else(|| line.strip_prefix("    "))
+}
+
+fn is_blank(line: &str) -> bool {
+    line.trim().is_empty()
+}
+
+/// Level of a `### Title` line.
+fn heading_level(line: &str) -> Option<usize> {
+    let level = line.chars().take_while(|&c| c == '#').count();
+    ((1..=6).contains(&level) && line[level..].starts_with(' ')).then_some(level)
+}
+
+/// Cells of a `| a | b |` table row.
+fn cells(row: &str) -> Vec<&str> {
+    let row = row.trim();
+    let row = row.strip_prefix('|').unwrap_or(row);
+    let row = row.strip_suffix('|').unwrap_or(row);
+    row.split('|').map(str::trim).collect()
+}
+
+/// A paragraph, lines ending in two spaces are followed by a line break.
+fn paragraph(lines: &[&str]) -> String {
+    let mut html = String::from("<p>");
+    for (i, line) in lines.iter().enumerate() {
+        html.push_str(&inline(line.trim()));
+        if i + 1 < lines.len() {
+            html.push_str(if line.ends_with("  ") { "<br>\n" } else { "\n" });
+        }
+    }
+    html.push_str("</p>\n");
+    html
+}
+
+/// Converts a sequence of block level elements.
+fn blocks(lines: &[&str]) -> String {
+    let mut html = String::new();
+    let mut text: Vec<&str> = vec![];
+    let mut i = 0;
+    while i < lines.len() {
+        let line = lines[i];
+        let starts_block = is_blank(line)
+            || line.starts_with("```")
+            || line.starts_with("!!! ")
+            || heading_level(line).is_some()
+            || line.starts_with('>')
+            || line.starts_with('|')
+            || line.starts_with("- ");
+        if !starts_block {
+            text.push(line);
+            i += 1;
+            continue;
+        }
+        if !text.is_empty() {
+            html.push_str(&paragraph(&text));
+            text.clear();
+        }
+
+        let end = if is_blank(line) {
+            i + 1
+        } else if let Some(language) = line.strip_prefix("```") {
+            let end = lines[i + 1..]
+                .iter()
+                .position(|line| line.trim_end() == "```")
+                .map_or(lines.len(), |n| i + 1 + n);
+            let class = if language.trim().is_empty() {
+                String::new()
+            } else {
+                format!(" class=\"language-{}\"", escape(language.trim()))
+            };
+            let mut code = lines[i + 1..end].join("\n");
+            if end > i + 1 {
+                code.push('\n');
+            }
+            html.push_str(&format!(
+                "<pre><code{}>{}</code></pre>\n",
+                class,
+                escape(&code)
+            ));
+            end + 1
+        } else if let Some(rest) = line.strip_prefix("!!! ") {
+            let (kind, title) = match rest.split_once(' ') {
+                Some((kind, title)) => (kind, title.trim().trim_matches('"').to_string()),
+                None => {
+                    let kind = rest.trim();
+                    let mut chars = kind.chars();
+                    let title = chars
+                        .next()
+                        .map(|c| c.to_uppercase().chain(chars).collect())
+                        .unwrap_or_default();
+                    (kind, title)
+                }
+            };
+            let end = lines[i + 1..]
+                .iter()
+                .position(|line| !is_blank(line) && dedent(line).is_none())
+                .map_or(lines.len(), |n| i + 1 + n);
+            let body: Vec<&str> = lines[i + 1..end]
+                .iter()
+                .map(|line| dedent(line).unwrap_or_default())
+                .collect();
+            html.push_str(&format!(
+                "<div class=\"admonition {}\">\n<p class=\"admonition-title\">{}</p>\n{}</div>\n",
+                escape(kind),
+                inline(&title),
+                blocks(&body)
+            ));
+            end
+        } else if let Some(level) = heading_level(line) {
+            let title = line[level..].trim();
+            html.push_str(&format!(
+                "<h{} id=\"{}\">{}</h{}>\n",
+                level,
+                escape(&slug(title)),
+                inline(title),
+                level
+            ));
+            i + 1
+        } else if line.starts_with('>') {
+            let end = lines[i..]
+                .iter()
+                .position(|line| !line.starts_with('>'))
+                .map_or(lines.len(), |n| i + n);
+            let quoted: Vec<&str> = lines[i..end]
+                .iter()
+                .map(|line| {
+                    let line = &line[1..];
+                    line.strip_prefix(' ').unwrap_or(line)
+                })
+                .collect();
+            html.push_str(&format!("<blockquote>\n{}</blockquote>\n", blocks(&quoted)));
+            end
+        } else if line.starts_with('|') {
+            let end = lines[i..]
+                .iter()
+                .position(|line| !line.starts_with('|'))
+                .map_or(lines.len(), |n| i + n);
+            html.push_str("<table>\n");
+            for (n, row) in lines[i..end].iter().enumerate() {
+                if n == 1 && row.contains("---") {
+                    continue;
+                }
+                let tag = if n == 0 { "th" } else { "td" };
+                html.push_str("<tr>");
+                for cell in cells(row) {
+                    html.push_str(&format!("<{}>{}</{}>", tag, inline(cell), tag));
+                }
+                html.push_str("</tr>\n");
+            }
+            html.push_str("</table>\n");
+            end
+        } else {
+            // a list, items continue on lines indented by two spaces
+            html.push_str("<ul>\n");
+            let mut end = i;
+            while let Some(item) = lines.get(end).and_then(|line| line.strip_prefix("- ")) {
+                let mut item = vec![item];
+                end += 1;
+                while let Some(line) = lines.get(end).and_then(|line| line.strip_prefix("  ")) {
+                    item.push(line);
+                    end += 1;
+                }
+                let item = paragraph(&item);
+                let item = &item["<p>".len()..item.len() - "</p>\n".len()];
+                html.push_str(&format!("<li>{}</li>\n", item));
+            }
+            html.push_str("</ul>\n");
+            end
+        };
+        i = end;
+    }
+    if !text.is_empty() {
+        html.push_str(&paragraph(&text));
+    }
+    html
+}
+
+/// Converts the Markdown of [`MkDocs`](super::MkDocs) or
+/// [`CommonMark`](super::CommonMark) to an HTML fragment.
+pub fn to_html(markdown: &str) -> String {
+    let lines: Vec<&str> = markdown.lines().collect();
+    blocks(&lines)
+}
+
+/// [`to_html`] as a standalone page styled with [`STYLE`].
+pub fn page(markdown: &str) -> String {
+    format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<style>\n{}</style>\n</head>\n<body>\n{}</body>\n</html>\n",
+        STYLE,
+        to_html(markdown)
+    )
+}
@@ -0,0 +1,261 @@
+//! Lightweight scanning of Daedalus code, for finding declarations without
+//! parsing the code in between.
+
+use std::{iter::Peekable, ops::Range, str::CharIndices};
+
+/// A top level statement of the input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Statement {
+    /// Offset of its first character.
+    pub start: usize,
+    /// The comments between the previous statement and this one, `//`
+    /// comments without their line end.
+    pub comments: Vec<Range<usize>>,
+}
+
+/// A piece of Daedalus code, see [`tokens`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Token {
+    /// A `//` comment without its line end, or a `/* */` comment.
+    Comment,
+    /// A string literal.
+    Str,
+    /// Any other character outside of whitespace.
+    Char(char),
+}
+
+/// Iterator over the [`Token`]s of some code and their byte ranges.
+pub(crate) struct Tokens<'a> {
+    input: &'a str,
+    chars: Peekable<CharIndices<'a>>,
+}
+
+/// Splits `input` into comments, strings and single characters, skipping
+/// whitespace. Unterminated comments and strings end with the input.
+pub(crate) fn tokens(input: &str) -> Tokens<'_> {
+    Tokens {
+        input,
+        chars: input.char_indices().peekable(),
+    }
+}
+
+impl Iterator for Tokens<'_> {
+    type Item = (Range<usize>, Token);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (start, c) = self.chars.find(|(_, c)| !c.is_whitespace())?;
+        let chars = &mut self.chars;
+        let (end, token) = match c {
+            '/' if matches!(chars.peek(), Some((_, '/'))) => {
+                let end = loop {
+                    match chars.peek() {
+                        Some(&(end, '\n')) => break end,
+                        Some(_) => {
+                            chars.next();
+                        }
+                        None => break self.input.len(),
+                    }
+                };
+                (end, Token::Comment)
+            }
+            '/' if matches!(chars.peek(), Some((_, '*'))) => {
+                chars.next();
+                let mut end = self.input.len();
+                while let Some((_, c)) = chars.next() {
+                    if c == '*' {
+                        if let Some((close, _)) = chars.next_if(|&(_, c)| c == '/') {
+                            end = close + 1;
+                            break;
+                        }
+                    }
+                }
+                (end, Token::Comment)
+            }
+            // Daedalus strings have no escapes and can not span lines
+            '"' => {
+                let mut end = self.input.len();
+                for (i, c) in chars.by_ref() {
+                    if c == '"' || c == '\n' {
+                        end = i + 1;
+                        break;
+                    }
+                }
+                (end, Token::Str)
+            }
+            c => (start + c.len_utf8(), Token::Char(c)),
+        };
+        Some((start..end, token))
+    }
+}
+
+/// The top level statements of `input` and the comments after the last one.
+/// A statement starts at the first character after the previous `;` outside
+/// of any braces, skipping whitespace and comments. Strings and comments
+/// never start or end a statement.
+fn scan(input: &str) -> (Vec<Statement>, Vec<Range<usize>>) {
+    let mut statements = vec![];
+    let mut comments = vec![];
+    let mut depth = 0usize;
+    let mut at_start = true;
+    for (range, token) in tokens(input) {
+        if token == Token::Comment {
+            if at_start && depth == 0 {
+                comments.push(range);
+            }
+            continue;
+        }
+        if at_start && depth == 0 {
+            statements.push(Statement {
+                start: range.start,
+                comments: std::mem::take(&mut comments),
+            });
+            at_start = false;
+        }
+        match token {
+            Token::Char('{') => depth += 1,
+            Token::Char('}') => depth = depth.saturating_sub(1),
+            Token::Char(';') if depth == 0 => at_start = true,
+            _ => {}
+        }
+    }
+    (statements, comments)
+}
+
+/// The top level statements of `input`, see [`Statement`].
+pub(crate) fn statements(input: &str) -> Vec<Statement> {
+    scan(input).0
+}
+
+/// Offset of the start of the line containing `offset`.
+pub(crate) fn line_start(input: &str, offset: usize) -> usize {
+    input[..offset].rfind('\n').map_or(0, |i| i + 1)
+}
+
+/// Whether `text` starts with `///` or `/**`, but not with a `////`,
+/// `/**/` or `/***` banner.
+pub(crate) fn starts_doc_comment(text: &str) -> bool {
+    (text.starts_with("///") && !text.starts_with("////"))
+        || (text.starts_with("/**") && !text.starts_with("/**/") && !text.starts_with("/***"))
+}
+
+/// Offset of the doc block directly above `statement`: of its first `///`,
+/// or of its `/**`. Empty lines may separate the block from the statement,
+/// but not the `///` lines of the block.
+pub(crate) fn doc_block(input: &str, statement: &Statement) -> Option<usize> {
+    let last = statement.comments.last()?;
+    if input[last.clone()].starts_with("/*") {
+        return starts_doc_comment(&input[last.clone()]).then_some(last.start);
+    }
+    let mut block: Option<usize> = None;
+    for comment in statement.comments.iter().rev() {
+        let starts_line = input[line_start(input, comment.start)..comment.start]
+            .trim()
+            .is_empty();
+        let next_line =
+            block.is_none_or(|next| input[comment.end..next].matches('\n').count() == 1);
+        if !starts_doc_comment(&input[comment.clone()]) || !starts_line || !next_line {
+            break;
+        }
+        block = Some(comment.start);
+    }
+    block
+}
+
+/// Whether `statement` directly follows a doc block.
+pub(crate) fn is_documented(input: &str, statement: &Statement) -> bool {
+    doc_block(input, statement).is_some()
+}
+
+/// Offsets of the top level doc blocks of `input`, see [`doc_block`]. A
+/// block at the end of `input` is included even though no statement follows.
+pub(crate) fn doc_blocks(input: &str) -> Vec<usize> {
+    let (mut statements, comments) = scan(input);
+    statements.push(Statement {
+        start: input.len(),
+        comments,
+    });
+    statements
+        .iter()
+        .filter_map(|statement| doc_block(input, statement))
+        .collect()
+}
+
+const KEYWORDS: [&str; 6] = ["func", "const", "var", "class", "prototype", "instance"];
+
+/// Lowercase keyword and name of the declaration starting at the beginning of
+/// `input`, e.g. `("func", "Doc_Show")` for `FUNC VOID Doc_Show()`, or `None`
+/// if it is not a declaration.
+pub(crate) fn declaration(input: &str) -> Option<(&'static str, &str)> {
+    let mut words = input
+        .split(|c: char| !c.is_ascii_alphanumeric() && c != '_')
+        .filter(|word| !word.is_empty());
+    let word = words.next()?;
+    if !input.starts_with(word) {
+        return None;
+    }
+    let keyword = KEYWORDS
+        .into_iter()
+        .find(|keyword| keyword.eq_ignore_ascii_case(word))?;
+    let name = match keyword {
+        "func" | "const" | "var" => words.nth(1)?,
+        "class" | "prototype" | "instance" => words.next()?,
+        _ => return None,
+    };
+    Some((keyword, name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn starts(input: &str) -> Vec<usize> {
+        statements(input).iter().map(|s| s.start).collect()
+    }
+
+    #[test]
+    fn braces_in_strings_and_comments() {
+        let input = "func void A() { s = \"}\"; // }\n /* { */ };\nconst int B = 1;\n";
+        assert_eq!(starts(input), [0, input.find("const").unwrap()]);
+    }
+
+    #[test]
+    fn block_comment_end_in_line_comment() {
+        let input =
+            "/** Doc A */\nfunc void A() {};\n/// Doc B\nfunc void B() {};\n// end of section */\n";
+        assert_eq!(doc_blocks(input), [0, input.find("/// Doc B").unwrap()]);
+    }
+
+    #[test]
+    fn line_comment_is_not_a_doc_block() {
+        let input = "/** A */\nfunc void A() {};\n// ---- */\nfunc void B() {};\n";
+        let statements = statements(input);
+        assert!(is_documented(input, &statements[0]));
+        assert!(!is_documented(input, &statements[1]));
+    }
+
+    #[test]
+    fn doc_line_ending_in_block_comment_end() {
+        let input = "/// Skips a comment like /* this */\nfunc void X() {};\n";
+        assert_eq!(doc_blocks(input), [0]);
+    }
+
+    #[test]
+    fn banners_are_not_doc_blocks() {
+        let input = "////////////\nfunc void A() {};\n/// B\n//// section\nfunc void B() {};\n";
+        assert!(doc_blocks(input).is_empty());
+        let input = "//// section\n/// C\nfunc void C() {};\n";
+        assert_eq!(doc_blocks(input), [input.find("/// C").unwrap()]);
+    }
+
+    #[test]
+    fn empty_line_ends_doc_block() {
+        let input = "/// A\n\n/// B\n\nfunc void B() {};\n";
+        assert_eq!(doc_blocks(input), [input.find("/// B").unwrap()]);
+    }
+
+    #[test]
+    fn doc_block_at_end() {
+        let input = "func void A() {};\n/// Trailing\n";
+        assert_eq!(doc_blocks(input), [input.find("///").unwrap()]);
+    }
+}
@@ -0,0 +1,189 @@
+//! Doc comment stubs for undocumented functions.
+//!
+//! ```
+//! let source = "func int Add(var int a, var int b) {\n\treturn a + b;\n};\n";
+//! assert_eq!(
+//!     ddcf::skeleton::insert_stubs(source),
+//!     "/// TODO\n///\n/// @param a\n/// @param b\n/// @return\n".to_string() + source
+//! );
+//! ```
+
+use crate::{
+    formatter::{self, Signature, TypeKind},
+    scan,
+};
+
+/// A stub to insert above an undocumented function.
+#[derive(Debug, Clone)]
+pub struct Stub {
+    /// Byte offset of the line the stub is inserted before.
+    pub offset: usize,
+    /// 1-based line of the function.
+    pub line: usize,
+    pub signature: Signature,
+    /// The `///` lines of the stub, indented like the function.
+    pub text: String,
+}
+
+fn stub_text(signature: &Signature, indent: &str, newline: &str) -> String {
+    let returns = TypeKind::of(&signature.return_type) != TypeKind::Void;
+    let mut lines = vec!["TODO".to_string()];
+    if !signature.params.is_empty() || returns {
+        lines.push(String::new());
+    }
+    for param in &signature.params {
+        lines.push(format!("@param {}", param.name));
+    }
+    if returns {
+        lines.push("@return".to_string());
+    }
+    lines
+        .iter()
+        .map(|line| {
+            if line.is_empty() {
+                format!("{}///{}", indent, newline)
+            } else {
+                format!("{}/// {}{}", indent, line, newline)
+            }
+        })
+        .collect()
+}
+
+/// Finds the top level functions of `input` without a `///` block, at most
+/// one per line. Code which does not parse is skipped.
+pub fn find_undocumented(input: &str) -> Vec<Stub> {
+    let newline = if input.contains("\r\n") { "\r\n" } else { "\n" };
+    let mut stubs: Vec<Stub> = scan::statements(input)
+        .into_iter()
+        .filter(|statement| {
+            scan::declaration(&input[statement.start..])
+                .is_some_and(|(keyword, _)| keyword == "func")
+        })
+        .filter(|statement| !scan::is_documented(input, statement))
+        .filter_map(|statement| {
+            let start = statement.start;
+            let signature = formatter::parse_function_signature(&input[start..]).ok()?;
+            let offset = scan::line_start(input, start);
+            let indent = &input[offset..start];
+            let indent = if indent.chars().all(char::is_whitespace) {
+                indent
+            } else {
+                ""
+            };
+            Some(Stub {
+                offset,
+                line: input[..start].matches('\n').count() + 1,
+                text: stub_text(&signature, indent, newline),
+                signature,
+            })
+        })
+        .collect();
+    // only the first of several functions on one line gets a stub, the
+    // stubs would merge into one block
+    stubs.dedup_by_key(|stub| stub.offset);
+    stubs
+}
+
+/// `input` with a stub inserted above every undocumented function.
+pub fn insert_stubs(input: &str) -> String {
+    let mut out = String::with_capacity(input.len());
+    let mut copied = 0;
+    for stub in find_undocumented(input) {
+        out.push_str(&input[copied..stub.offset]);
+        out.push_str(&stub.text);
+        copied = stub.offset;
+    }
+    out.push_str(&input[copied..]);
+    out
+}
+
+/// Unified diff (with three lines of context) inserting the stubs of
+/// `input`, a file at `path`. Empty if there is nothing to insert.
+pub fn stubs_diff(input: &str, path: &str) -> String {
+    const CONTEXT: usize = 3;
+    let stubs = find_undocumented(input);
+    if stubs.is_empty() {
+        return String::new();
+    }
+    let lines: Vec<&str> = input.split_inclusive('\n').collect();
+    // index of the line each stub is inserted before
+    let at: Vec<usize> = stubs
+        .iter()
+        .map(|stub| input[..stub.offset].matches('\n').count())
+        .collect();
+
+    let mut diff = format!("--- a/{}\n+++ b/{}\n", path, path);
+    let mut added = 0;
+    let mut i = 0;
+    while i < stubs.len() {
+        // stubs i..j share a hunk, as their context overlaps
+        let start = at[i].saturating_sub(CONTEXT);
+        let mut end = (at[i] + CONTEXT).min(lines.len());
+        let mut j = i + 1;
+        while j < stubs.len() && at[j].saturating_sub(CONTEXT) <= end {
+            end = (at[j] + CONTEXT).min(lines.len());
+            j += 1;
+        }
+
+        let mut hunk = String::new();
+        let mut new_len = 0;
+        let mut next = i;
+        for (index, line) in lines.iter().enumerate().take(end).skip(start) {
+            if next < j && at[next] == index {
+                for stub_line in stubs[next].text.split_inclusive('\n') {
+                    hunk.push('+');
+                    hunk.push_str(stub_line);
+                    new_len += 1;
+                }
+                next += 1;
+            }
+            hunk.push(' ');
+            hunk.push_str(line);
+            if !line.ends_with('\n') {
+                hunk.push_str("\n\\ No newline at end of file\n");
+            }
+            new_len += 1;
+        }
+        diff.push_str(&format!(
+            "@@ -{},{} +{},{} @@\n",
+            start + 1,
+            end - start,
+            start + 1 + added,
+            new_len
+        ));
+        diff.push_str(&hunk);
+        added += new_len - (end - start);
+        i = j;
+    }
+    diff
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn one_stub_per_line() {
+        let input = "func void A() {}; func void B(var int b) {};\n";
+        let stubs = find_undocumented(input);
+        assert_eq!(stubs.len(), 1);
+        assert_eq!(stubs[0].signature.name, "A");
+        assert_eq!(insert_stubs(input), "/// TODO\n".to_string() + input);
+        assert_eq!(
+            stubs_diff(input, "a.d"),
+            "--- a/a.d\n+++ b/a.d\n@@ -1,1 +1,2 @@\n+/// TODO\n func void A() {}; func void B(var int b) {};\n"
+        );
+    }
+
+    #[test]
+    fn diff_hunks() {
+        let input = "func void A() {};\n\n\n\n\n\n\n\n/// B\nfunc void B() {};\nfunc int C() {};\n";
+        assert_eq!(
+            stubs_diff(input, "a.d"),
+            "--- a/a.d\n+++ b/a.d\n\
+             @@ -1,3 +1,4 @@\n+/// TODO\n func void A() {};\n \n \n\
+             @@ -8,4 +9,7 @@\n \n /// B\n func void B() {};\n+/// TODO\n+///\n+/// @return\n func int C() {};\n"
+        );
+        assert!(stubs_diff("/// A\nfunc void A() {};\n", "a.d").is_empty());
+    }
+}